@@ -0,0 +1,87 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use near_account_id::{AccountId, AccountIdRef};
+
+const INPUTS: &[(&str, &str)] = &[
+    ("short", "alice.near"),
+    (
+        "max-length",
+        "app-01.alice_02.near-03.alice_04.near-05.alice_06.near-07.alice0",
+    ),
+    (
+        "near-implicit",
+        "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+    ),
+];
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in INPUTS {
+        group.bench_function(format!("AccountIdRef::parse/{}", name), |b| {
+            b.iter(|| AccountIdRef::parse(black_box(input)).unwrap())
+        });
+        group.bench_function(format!("AccountId::validate/{}", name), |b| {
+            b.iter(|| AccountId::validate(black_box(input)).unwrap())
+        });
+        group.bench_function(format!("AccountId::from_str/{}", name), |b| {
+            b.iter(|| black_box(input).parse::<AccountId>().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn validate_64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate_64");
+    // Separator-free 64 byte inputs take the bytewise fast path, while the others
+    // go through the full state machine.
+    let inputs = [
+        (
+            "near-implicit",
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de".to_string(),
+        ),
+        ("named", "a".repeat(64)),
+        (
+            "with-separators",
+            format!("{}.near", "a-b".repeat(19) + "ab"),
+        ),
+    ];
+    for (name, input) in &inputs {
+        assert_eq!(input.len(), 64);
+        group.bench_function(*name, |b| {
+            b.iter(|| AccountId::validate(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn fast_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fast_path");
+    // The same 64 byte hex input, validated with and without the bytewise fast path.
+    // `AccountIdRef::first_invalid` only runs the state machine that `validate` falls back to.
+    let input = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+    let account_id = AccountIdRef::new_or_panic(input);
+    group.bench_function("fast-path", |b| {
+        b.iter(|| AccountId::validate(black_box(input)).unwrap())
+    });
+    group.bench_function("state-machine", |b| {
+        b.iter(|| assert!(black_box(account_id).first_invalid().is_none()))
+    });
+    group.finish();
+}
+
+fn invalid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("invalid");
+    let inputs = [
+        ("invalid-char", "alice.\u{192}elicia.near".to_string()),
+        ("redundant-separator", "alice..near".to_string()),
+        ("too-long", "a".repeat(65)),
+    ];
+    for (name, input) in &inputs {
+        group.bench_function(*name, |b| {
+            b.iter(|| AccountId::validate(black_box(input)).unwrap_err())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, validate_64, fast_path, invalid);
+criterion_main!(benches);
@@ -1,6 +1,13 @@
-use std::{borrow::Cow, fmt, ops::Deref, str::FromStr};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    format,
+    string::String,
+    vec::Vec,
+};
+use core::{fmt, ops::Deref, str::FromStr};
 
-use crate::{AccountIdRef, ParseAccountError};
+use crate::{AccountIdRef, ParseAccountError, ParseErrorKind};
 
 /// NEAR Account Identifier.
 ///
@@ -10,6 +17,9 @@ use crate::{AccountIdRef, ParseAccountError};
 ///
 /// Also see [Error kind precedence](AccountId#error-kind-precedence).
 ///
+/// Account IDs are ordered byte-lexicographically by their string representation, which is
+/// exactly what they serialize to. This ordering is stable and survives serialization round-trips.
+///
 /// ## Examples
 ///
 /// ```
@@ -22,13 +32,26 @@ use crate::{AccountIdRef, ParseAccountError};
 #[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
-pub struct AccountId(pub(crate) Box<str>);
+pub struct AccountId(
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(length(min = 2, max = 64), regex(path = "crate::ACCOUNT_ID_PATTERN"))
+    )]
+    pub(crate) Box<str>,
+);
 
 impl AccountId {
     /// Shortest valid length for a NEAR Account ID.
     pub const MIN_LEN: usize = crate::validation::MIN_LEN;
     /// Longest valid length for a NEAR Account ID.
     pub const MAX_LEN: usize = crate::validation::MAX_LEN;
+    /// Most labels a valid NEAR Account ID can have, i.e. `32`.
+    ///
+    /// Each label takes at least two of the [`MAX_LEN`](Self::MAX_LEN) characters, one for
+    /// itself and one for the following `.`, except for the last one. This also bounds how
+    /// many times [`AccountIdRef::get_parent_account_id`] can be applied, so arrays of this
+    /// size can hold all labels or ancestors of any Account ID.
+    pub const MAX_LABELS: usize = crate::validation::MAX_LABELS;
 
     /// Creates an `AccountId` without any validation checks.
     ///
@@ -115,195 +138,988 @@ impl AccountId {
     pub fn validate(account_id: &str) -> Result<(), ParseAccountError> {
         crate::validation::validate(account_id)
     }
-}
 
-impl AsRef<str> for AccountId {
-    fn as_ref(&self) -> &str {
-        &self.0
+    /// Validates a single label of an Account ID, i.e. a part between `.` separators.
+    ///
+    /// This is useful to validate a sub-account name before creating it with
+    /// [`AccountIdRef::make_sub_account`]. A label must be `1..=`[`AccountId::MAX_LEN`]
+    /// characters long, must not contain a `.`, and otherwise follows the rules of
+    /// [`AccountId::validate`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// assert!(AccountId::validate_label("a-b").is_ok());
+    ///
+    /// let err = AccountId::validate_label("a.b").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    ///
+    /// let err = AccountId::validate_label("-a").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+    ///
+    /// let err = AccountId::validate_label("").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+    /// ```
+    pub fn validate_label(label: &str) -> Result<(), ParseAccountError> {
+        crate::validation::validate_label(label)
     }
-}
 
-impl AsRef<AccountIdRef> for AccountId {
-    fn as_ref(&self) -> &AccountIdRef {
-        self
+    /// Validates a string as a well-structured NEAR Account ID in const context.
+    ///
+    /// Unlike [`AccountIdRef::new_or_panic`], this doesn't panic, but reports the kind of the
+    /// violation, so that macros can compose several checks and report errors their own way.
+    /// See [`AccountId::validate`] for the error precedence.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// const ALICE_IS_VALID: bool = AccountId::validate_const_result("alice.near").is_ok();
+    /// assert!(ALICE_IS_VALID);
+    ///
+    /// const REASON: &str = match AccountId::validate_const_result("alice..near") {
+    ///     Ok(()) => "valid",
+    ///     Err(ParseErrorKind::RedundantSeparator) => "redundant separator",
+    ///     Err(_) => "other",
+    /// };
+    /// assert_eq!(REASON, "redundant separator");
+    /// ```
+    pub const fn validate_const_result(account_id: &str) -> Result<(), ParseErrorKind> {
+        crate::validation::validate_const_result(account_id)
     }
-}
-
-impl Deref for AccountId {
-    type Target = AccountIdRef;
 
-    fn deref(&self) -> &Self::Target {
-        AccountIdRef::new_unvalidated(&self.0)
+    /// Validates a string as a well-structured NEAR Account ID that isn't reserved by the protocol.
+    ///
+    /// Reserved Account IDs (currently only [`system`](AccountIdRef::SYSTEM)) are valid Account
+    /// IDs, and appear on-chain, so [`AccountId::validate`] and parsing accept them. This is meant
+    /// for user input instead, and reports them as [`ParseErrorKind::Reserved`](crate::ParseErrorKind::Reserved)
+    /// so a UI can explain why they can't be used.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// assert!(AccountId::validate("system").is_ok());
+    ///
+    /// let err = AccountId::validate_strict("system").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::Reserved);
+    ///
+    /// assert!(AccountId::validate_strict("alice.near").is_ok());
+    /// ```
+    pub fn validate_strict(account_id: &str) -> Result<(), ParseAccountError> {
+        crate::validation::validate_strict(account_id)
     }
-}
 
-impl std::borrow::Borrow<AccountIdRef> for AccountId {
-    fn borrow(&self) -> &AccountIdRef {
-        AccountIdRef::new_unvalidated(self)
+    /// Validates a string as a well-structured NEAR Account ID, with a custom maximum length.
+    ///
+    /// Per [NEP-492](https://github.com/near/NEPs/pull/492), the registrar is allowed to create
+    /// top-level accounts longer than [`AccountId::MAX_LEN`]. This allows opting into a higher
+    /// ceiling, while [`AccountId::validate`] keeps enforcing the default one.
+    ///
+    /// Implicit accounts are unaffected by the limit, they are always 64 (NEAR-implicit)
+    /// or 42 (ETH-implicit) characters long.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let long_tla = "a".repeat(100);
+    ///
+    /// assert!(AccountId::validate(&long_tla).is_err());
+    /// assert!(AccountId::validate_with_limits(&long_tla, 128).is_ok());
+    /// ```
+    pub fn validate_with_limits(account_id: &str, max_len: usize) -> Result<(), ParseAccountError> {
+        crate::validation::validate_with_limits(account_id, max_len)
     }
-}
 
-impl FromStr for AccountId {
-    type Err = ParseAccountError;
+    /// Parses a string as an `AccountId`.
+    ///
+    /// This is the same as [`str::parse`] or [`FromStr::from_str`], without having to name
+    /// the target type or import the trait.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let alice = AccountId::parse("alice.near").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// assert!(AccountId::parse("Alice.near").is_err());
+    /// ```
+    pub fn parse(account_id: &str) -> Result<Self, ParseAccountError> {
+        account_id.parse()
+    }
 
-    fn from_str(account_id: &str) -> Result<Self, Self::Err> {
-        crate::validation::validate(account_id)?;
+    /// Parses a string as an `AccountId`, with a custom maximum length.
+    ///
+    /// See [`AccountId::validate_with_limits`] for details.
+    ///
+    /// Note that Account IDs longer than [`AccountId::MAX_LEN`] are still rejected by
+    /// the default parsing and deserialization paths.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let long_tla = "a".repeat(100);
+    ///
+    /// assert!(long_tla.parse::<AccountId>().is_err());
+    ///
+    /// let account_id = AccountId::parse_with_limits(&long_tla, 128).unwrap();
+    /// assert!(account_id.is_top_level());
+    /// ```
+    pub fn parse_with_limits(account_id: &str, max_len: usize) -> Result<Self, ParseAccountError> {
+        crate::validation::validate_with_limits(account_id, max_len)?;
         Ok(Self(account_id.into()))
     }
-}
-
-impl TryFrom<Box<str>> for AccountId {
-    type Error = ParseAccountError;
 
-    fn try_from(account_id: Box<str>) -> Result<Self, Self::Error> {
-        crate::validation::validate(&account_id)?;
-        Ok(Self(account_id))
+    /// Parses a byte slice as an `AccountId`.
+    ///
+    /// Since the allowed alphabet is pure ASCII, the bytes are validated directly, without
+    /// a separate UTF-8 validation pass. Any non-ASCII byte is reported as
+    /// [`ParseErrorKind::InvalidChar`](crate::ParseErrorKind::InvalidChar).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::from_utf8(b"alice.near").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let err = AccountId::from_utf8(b"alice\xff.near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn from_utf8(account_id: &[u8]) -> Result<Self, ParseAccountError> {
+        Ok(AccountIdRef::from_bytes(account_id)?.to_owned())
     }
-}
 
-impl TryFrom<String> for AccountId {
-    type Error = ParseAccountError;
+    /// Parses an Account ID from a stream of characters.
+    ///
+    /// The characters are validated as they are read, like with a [`Validator`](crate::Validator),
+    /// so the first invalid character or redundant separator fails right away. An overlong
+    /// stream fails with [`ParseErrorKind::TooLong`](crate::ParseErrorKind::TooLong) as soon as it
+    /// exceeds [`AccountId::MAX_LEN`] bytes, without reading the rest of it or buffering more than
+    /// that.
+    ///
+    /// Valid Account IDs are accepted exactly like parsing the collected string does, but an
+    /// invalid one may be reported with a different error, since the first violation in the
+    /// stream is reported rather than following the
+    /// [error kind precedence](AccountId#error-kind-precedence).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::try_from_chars("alice.near".chars()).unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let err = AccountId::try_from_chars(std::iter::repeat('a')).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+    /// ```
+    pub fn try_from_chars<I: IntoIterator<Item = char>>(
+        chars: I,
+    ) -> Result<Self, ParseAccountError> {
+        let mut validator = crate::Validator::new();
+        let mut account_id = [0; Self::MAX_LEN];
+        let mut len = 0;
+        for c in chars {
+            validator.push(c.encode_utf8(&mut [0; 4]).as_bytes())?;
+            // The validator only accepts ASCII characters, and at most `MAX_LEN` of them.
+            account_id[len] = c as u8;
+            len += 1;
+        }
+        validator.finish()?;
 
-    fn try_from(account_id: String) -> Result<Self, Self::Error> {
-        crate::validation::validate(&account_id)?;
-        Ok(Self(account_id.into_boxed_str()))
+        // Safety: a validated Account ID only consists of ASCII characters, which is valid UTF-8.
+        let account_id = unsafe { core::str::from_utf8_unchecked(&account_id[..len]) };
+        Ok(Self(account_id.into()))
     }
-}
 
-impl fmt::Display for AccountId {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+    /// Validates the contents of `buf` and moves them into a new `AccountId`, leaving `buf` empty
+    /// but with its capacity intact, so it can be reused for building the next Account ID.
+    ///
+    /// This makes exactly one allocation, the size of the Account ID. In contrast,
+    /// `TryFrom<String>` takes over the allocation
+    /// of the `String`, which has to be shrunk (reallocated) if it has spare capacity.
+    ///
+    /// If validation fails, `buf` is left untouched.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let mut buf = String::with_capacity(64);
+    /// let mut account_ids = vec![];
+    /// for name in ["alice", "bob"] {
+    ///     buf.push_str(name);
+    ///     buf.push_str(".near");
+    ///     account_ids.push(AccountId::take_from_buf(&mut buf).unwrap());
+    /// }
+    ///
+    /// assert_eq!(account_ids, ["alice.near", "bob.near"]);
+    /// assert!(buf.is_empty() && buf.capacity() >= 64);
+    /// ```
+    pub fn take_from_buf(buf: &mut String) -> Result<Self, ParseAccountError> {
+        let account_id = buf.parse()?;
+        buf.clear();
+        Ok(account_id)
     }
-}
 
-impl From<AccountId> for String {
-    fn from(account_id: AccountId) -> Self {
-        account_id.0.into_string()
+    /// Borrows the `AccountId` as an [`AccountIdRef`].
+    ///
+    /// This is what [`Deref`] and [`AsRef<AccountIdRef>`] do, but spelled out, for generic
+    /// code where deref coercion doesn't apply, or where `as_ref` would be ambiguous with
+    /// [`AsRef<str>`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// let alice_ref: &AccountIdRef = alice.as_account_id_ref();
+    /// assert_eq!(alice_ref, "alice.near");
+    /// ```
+    pub fn as_account_id_ref(&self) -> &AccountIdRef {
+        AccountIdRef::new_unvalidated(&self.0)
     }
-}
 
-impl From<AccountId> for Box<str> {
-    fn from(value: AccountId) -> Box<str> {
-        value.0
+    /// Converts the `AccountId` into a [`String`].
+    ///
+    /// This consumes the `AccountId` and reuses its allocation, so nothing is copied.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// assert_eq!(alice.into_string(), "alice.near");
+    /// ```
+    pub fn into_string(self) -> String {
+        self.into()
     }
-}
 
-impl PartialEq<AccountId> for AccountIdRef {
-    fn eq(&self, other: &AccountId) -> bool {
-        &self.0 == other.as_str()
+    /// Converts the `AccountId` into a [`Box<str>`].
+    ///
+    /// This consumes the `AccountId` and reuses its allocation, so nothing is copied.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// assert_eq!(&*alice.into_boxed_str(), "alice.near");
+    /// ```
+    pub fn into_boxed_str(self) -> Box<str> {
+        self.into()
     }
-}
 
-impl PartialEq<AccountIdRef> for AccountId {
-    fn eq(&self, other: &AccountIdRef) -> bool {
-        self.as_str() == &other.0
+    /// Returns the system account.
+    ///
+    /// See [`AccountIdRef::SYSTEM`] for a non-allocating constant.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// assert!(AccountId::system().is_system());
+    /// ```
+    pub fn system() -> Self {
+        AccountIdRef::SYSTEM.to_owned()
     }
-}
 
-impl<'a> PartialEq<AccountId> for &'a AccountIdRef {
-    fn eq(&self, other: &AccountId) -> bool {
-        &self.0 == other.as_str()
+    /// Creates the NEAR-implicit `AccountId` of an ed25519 public key.
+    ///
+    /// The NEAR-implicit account ID is the lowercase hex encoding of the 32-byte public key,
+    /// which is always a valid Account ID.
+    ///
+    /// See [Implicit-Accounts](https://docs.near.org/docs/concepts/account#implicit-accounts).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountType};
+    ///
+    /// let account_id = AccountId::from_near_implicit(&[0xab; 32]);
+    ///
+    /// assert_eq!(account_id, "ab".repeat(32));
+    /// assert!(account_id.get_account_type() == AccountType::NearImplicitAccount);
+    /// ```
+    pub fn from_near_implicit(public_key: &[u8; 32]) -> Self {
+        let mut account_id = String::with_capacity(64);
+        crate::hex::encode(public_key, &mut account_id);
+        Self(account_id.into_boxed_str())
     }
-}
 
-impl<'a> PartialEq<&'a AccountIdRef> for AccountId {
-    fn eq(&self, other: &&'a AccountIdRef) -> bool {
-        self.as_str() == &other.0
+    /// Returns a [clap](https://docs.rs/clap) parser for Account ID arguments.
+    ///
+    /// See [`AccountIdValueParser`](crate::clap::AccountIdValueParser) for how errors are
+    /// reported.
+    #[cfg(feature = "clap")]
+    pub fn value_parser() -> crate::clap::AccountIdValueParser {
+        crate::clap::AccountIdValueParser::new()
     }
-}
 
-impl PartialEq<AccountId> for String {
-    fn eq(&self, other: &AccountId) -> bool {
-        self == other.as_str()
-    }
-}
+    /// Generates a random named `AccountId` of at most `max_len` characters.
+    ///
+    /// Labels follow the same rules as [`NamedAccountId`](crate::arbitrary::NamedAccountId):
+    /// the result never looks like an implicit account. `max_len` is clamped to
+    /// [`AccountId::MIN_LEN`]`..=`[`AccountId::MAX_LEN`].
+    ///
+    /// This is meant for test fixtures and sample data. The generated Account ID is only as
+    /// unpredictable as `rng`, and carries no cryptographic meaning.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountType};
+    ///
+    /// let mut rng = rand::rngs::mock::StepRng::new(0, 0x0123_4567_89ab_cdef);
+    /// let account_id = AccountId::random_named(&mut rng, 16);
+    ///
+    /// assert!(account_id.len() <= 16);
+    /// assert_eq!(account_id.get_account_type(), AccountType::NamedAccount);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random_named<R: rand::Rng + ?Sized>(rng: &mut R, max_len: usize) -> Self {
+        use crate::validation::{ACCOUNT_ID_CHARSET, MAX_LEN, MIN_LEN, SEPARATORS};
 
-impl PartialEq<String> for AccountId {
-    fn eq(&self, other: &String) -> bool {
-        self.as_str() == other
-    }
-}
+        let len = rng.gen_range(MIN_LEN..=max_len.clamp(MIN_LEN, MAX_LEN));
+        let mut account_id = String::with_capacity(len);
+        let mut last_char_is_separator = true;
+        for i in 0..len {
+            // Separators can't start or end the Account ID, nor follow each other.
+            let separator_allowed = !last_char_is_separator && i + 1 < len;
+            let c = loop {
+                let c = ACCOUNT_ID_CHARSET[rng.gen_range(0..ACCOUNT_ID_CHARSET.len())];
+                if separator_allowed || !SEPARATORS.contains(&c) {
+                    break c;
+                }
+            };
+            last_char_is_separator = SEPARATORS.contains(&c);
+            account_id.push(c as char);
+        }
 
-impl PartialEq<AccountId> for str {
-    fn eq(&self, other: &AccountId) -> bool {
-        self == other.as_str()
+        // Implicit accounts are all hex digits after their prefix, so `z` makes it named.
+        if crate::is_near_implicit(&account_id)
+            || crate::is_eth_implicit(&account_id)
+            || crate::is_near_deterministic(&account_id)
+        {
+            account_id.pop();
+            account_id.push('z');
+        }
+        Self(account_id.into_boxed_str())
     }
-}
 
-impl PartialEq<str> for AccountId {
-    fn eq(&self, other: &str) -> bool {
-        self.as_str() == other
+    /// Generates a random NEAR-implicit, ETH-implicit or NEAR-deterministic `AccountId`.
+    ///
+    /// Each account type is equally likely. Like [`AccountId::random_named`], this is meant
+    /// for test fixtures and sample data: the Account ID doesn't correspond to any key pair.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let mut rng = rand::rngs::mock::StepRng::new(0, 0x0123_4567_89ab_cdef);
+    /// let account_id = AccountId::random_implicit(&mut rng);
+    ///
+    /// assert!(account_id.is_implicit());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random_implicit<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..3) {
+            0 => Self::from_near_implicit(&rng.gen()),
+            1 => Self::from_eth_implicit(&rng.gen()),
+            _ => {
+                let mut account_id = String::with_capacity(42);
+                account_id.push_str("0s");
+                crate::hex::encode(&rng.gen::<[u8; 20]>(), &mut account_id);
+                Self(account_id.into_boxed_str())
+            }
+        }
     }
-}
 
-impl<'a> PartialEq<AccountId> for &'a str {
-    fn eq(&self, other: &AccountId) -> bool {
-        *self == other.as_str()
+    /// Creates the ETH-implicit `AccountId` of a 20-byte Ethereum address.
+    ///
+    /// The ETH-implicit account ID is `0x` followed by the lowercase hex encoding of the address,
+    /// which is always a valid Account ID.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountType};
+    ///
+    /// let account_id = AccountId::from_eth_implicit(&[0xab; 20]);
+    ///
+    /// assert_eq!(account_id, format!("0x{}", "ab".repeat(20)));
+    /// assert!(account_id.get_account_type() == AccountType::EthImplicitAccount);
+    /// ```
+    pub fn from_eth_implicit(address: &[u8; 20]) -> Self {
+        let mut account_id = String::with_capacity(42);
+        account_id.push_str("0x");
+        crate::hex::encode(address, &mut account_id);
+        Self(account_id.into_boxed_str())
     }
-}
 
-impl<'a> PartialEq<&'a str> for AccountId {
-    fn eq(&self, other: &&'a str) -> bool {
-        self.as_str() == *other
+    /// Validates a string as a well-structured NEAR Account ID, reporting every violation.
+    ///
+    /// Unlike [`AccountId::validate`], this doesn't stop at the first violation, but keeps scanning
+    /// the Account ID and reports all of them in order of appearance. A length violation, if any,
+    /// is always reported first, followed by every invalid character or redundant separator.
+    ///
+    /// The first reported error is always the same as the one returned by [`AccountId::validate`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// assert!(AccountId::validate_all("alice.near").is_ok());
+    ///
+    /// let errors = AccountId::validate_all("Alice..near").unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].kind(), &ParseErrorKind::InvalidChar);
+    /// assert_eq!(errors[1].kind(), &ParseErrorKind::RedundantSeparator);
+    /// ```
+    pub fn validate_all(account_id: &str) -> Result<(), Vec<ParseAccountError>> {
+        crate::validation::validate_all(account_id)
+    }
+
+    /// Parses every string of an iterator as an Account ID.
+    ///
+    /// Parsing stops at the first invalid Account ID, which is returned alongside its
+    /// position in the iterator.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let account_ids = AccountId::parse_many(["alice.near", "bob.near"]).unwrap();
+    /// assert_eq!(account_ids, ["alice.near", "bob.near"]);
+    ///
+    /// let (idx, err) = AccountId::parse_many(["alice.near", "Bob.near"]).unwrap_err();
+    /// assert_eq!(idx, 1);
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn parse_many<I, S>(iter: I) -> Result<Vec<Self>, (usize, ParseAccountError)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        iter.into_iter()
+            .enumerate()
+            .map(|(idx, account_id)| account_id.as_ref().parse().map_err(|err| (idx, err)))
+            .collect()
+    }
+
+    /// Assembles an Account ID from its labels, joining them with `.`.
+    ///
+    /// This is the inverse of [`AccountIdRef::labels`]. The joined Account ID is validated
+    /// as a whole, so errors report positions in the joined Account ID. A label that contains
+    /// a `.` itself is reported as [`ParseErrorKind::InvalidChar`](crate::ParseErrorKind::InvalidChar).
+    /// See [`AccountIdBuilder`](crate::AccountIdBuilder) to push labels one at a time.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice_app = AccountId::from_labels(["app", "alice", "near"]).unwrap();
+    /// assert_eq!(alice_app, "app.alice.near");
+    ///
+    /// let err = AccountId::from_labels(["app", "", "near"]).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+    /// ```
+    pub fn from_labels<I, S>(labels: I) -> Result<Self, ParseAccountError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut builder = crate::AccountIdBuilder::new();
+        builder.extend(labels);
+        builder.build()
+    }
+
+    /// Parses an Account ID after lowercasing its ASCII letters, returning whether that changed it.
+    ///
+    /// Only ASCII case folding is applied: non-ASCII letters are still rejected as
+    /// [`ParseErrorKind::InvalidChar`](crate::ParseErrorKind::InvalidChar), so lookalikes
+    /// can't sneak in.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let (alice, changed) = AccountId::parse_lossy("Alice.NEAR").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    /// assert!(changed);
+    ///
+    /// let (bob, changed) = AccountId::parse_lossy("bob.near").unwrap();
+    /// assert_eq!(bob, "bob.near");
+    /// assert!(!changed);
+    ///
+    /// let err = AccountId::parse_lossy("ƒelicia.near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn parse_lossy(account_id: &str) -> Result<(Self, bool), ParseAccountError> {
+        if account_id.bytes().any(|b| b.is_ascii_uppercase()) {
+            Ok((account_id.to_ascii_lowercase().try_into()?, true))
+        } else {
+            Ok((account_id.parse()?, false))
+        }
+    }
+
+    /// Parses an Account ID, accepting NEAR-implicit and ETH-implicit accounts in any ASCII case.
+    ///
+    /// Ethereum addresses are commonly written in mixed case (EIP-55), and hex is sometimes
+    /// uppercased. If the input is an implicit account except for its case, it's lowercased.
+    /// Anything else is parsed as is, so named accounts like `Alice.near` are still rejected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountType};
+    ///
+    /// let eth = AccountId::parse_allowing_hex_case("0xB794F5eA0ba39494cE839613fffBA74279579268").unwrap();
+    /// assert_eq!(eth, "0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert_eq!(eth.get_account_type(), AccountType::EthImplicitAccount);
+    ///
+    /// assert!(AccountId::parse_allowing_hex_case("Alice.near").is_err());
+    /// ```
+    pub fn parse_allowing_hex_case(account_id: &str) -> Result<Self, ParseAccountError> {
+        if account_id.bytes().any(|b| b.is_ascii_uppercase()) {
+            let lowercase = account_id.to_ascii_lowercase();
+            if crate::is_near_implicit(&lowercase) || crate::is_eth_implicit(&lowercase) {
+                return Ok(Self(lowercase.into_boxed_str()));
+            }
+        }
+        account_id.parse()
+    }
+
+    /// Parses an Account ID, suggesting a repaired Account ID if parsing fails.
+    ///
+    /// On failure, the original error is returned along with the result of a few simple repairs
+    /// (lowercasing, collapsing repeated separators and trimming separators and whitespace off
+    /// the edges), if that is a valid Account ID.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let (err, suggestion) = AccountId::parse_or_suggest("Alice..near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// assert_eq!(suggestion.unwrap(), "alice.near");
+    ///
+    /// let (_, suggestion) = AccountId::parse_or_suggest("alice@near").unwrap_err();
+    /// assert!(suggestion.is_none());
+    /// ```
+    pub fn parse_or_suggest(
+        account_id: &str,
+    ) -> Result<Self, (ParseAccountError, Option<AccountId>)> {
+        account_id
+            .parse()
+            .map_err(|err| (err, crate::validation::repair(account_id).try_into().ok()))
+    }
+
+    /// Swaps the top-level account `from` for `to` in each of the Account IDs, e.g. to migrate
+    /// a set of accounts from `testnet` to `near`.
+    ///
+    /// Account IDs under a different top-level account are returned unchanged. Rebased Account IDs
+    /// are re-validated, since their length changes, so each one gets its own result.
+    ///
+    /// Returns [`NotTopLevelAccount`](crate::NotTopLevelAccount) if `from` or `to` is not a
+    /// [top-level account](AccountIdRef::is_top_level).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let account_ids: Vec<AccountId> = ["alice.testnet", "bob.near"]
+    ///     .iter()
+    ///     .map(|account_id| account_id.parse().unwrap())
+    ///     .collect();
+    ///
+    /// let rebased = AccountId::rebase_tla(
+    ///     &account_ids,
+    ///     AccountIdRef::new_or_panic("testnet"),
+    ///     AccountIdRef::new_or_panic("near"),
+    /// );
+    /// assert_eq!(
+    ///     rebased.unwrap(),
+    ///     [Ok("alice.near".parse().unwrap()), Ok("bob.near".parse().unwrap())]
+    /// );
+    ///
+    /// let rebased = AccountId::rebase_tla(
+    ///     &account_ids,
+    ///     AccountIdRef::new_or_panic("alice.testnet"),
+    ///     AccountIdRef::new_or_panic("near"),
+    /// );
+    /// assert!(rebased.is_err());
+    /// ```
+    pub fn rebase_tla(
+        account_ids: &[AccountId],
+        from: &AccountIdRef,
+        to: &AccountIdRef,
+    ) -> Result<Vec<Result<AccountId, ParseAccountError>>, crate::NotTopLevelAccount> {
+        if !(from.is_top_level() && to.is_top_level()) {
+            return Err(crate::NotTopLevelAccount(()));
+        }
+
+        Ok(account_ids
+            .iter()
+            .map(
+                |account_id| match account_id.as_str().strip_suffix(from.as_str()) {
+                    Some("") => Ok(to.to_owned()),
+                    Some(sub_account) if sub_account.ends_with('.') => {
+                        format!("{}{}", sub_account, to).try_into()
+                    }
+                    _ => Ok(account_id.clone()),
+                },
+            )
+            .collect())
+    }
+}
+
+impl AsRef<str> for AccountId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<AccountIdRef> for AccountId {
+    fn as_ref(&self) -> &AccountIdRef {
+        self
+    }
+}
+
+impl Deref for AccountId {
+    type Target = AccountIdRef;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_account_id_ref()
+    }
+}
+
+// `Borrow` requires `Hash`, `Eq` and `Ord` to agree between the borrowed and owned forms.
+// `AccountId`, `AccountIdRef` and `str` all derive them from the same underlying `str`,
+// see `test_hash_consistent_with_borrow`.
+impl core::borrow::Borrow<AccountIdRef> for AccountId {
+    fn borrow(&self) -> &AccountIdRef {
+        AccountIdRef::new_unvalidated(self)
+    }
+}
+
+impl core::borrow::Borrow<str> for AccountId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for AccountId {
+    type Err = ParseAccountError;
+
+    fn from_str(account_id: &str) -> Result<Self, Self::Err> {
+        crate::validation::validate(account_id)?;
+        Ok(Self(account_id.into()))
+    }
+}
+
+impl TryFrom<Box<str>> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: Box<str>) -> Result<Self, Self::Error> {
+        crate::validation::validate(&account_id)?;
+        Ok(Self(account_id))
+    }
+}
+
+/// Validates the `String` and takes over its allocation.
+///
+/// If the `String` has spare capacity, it's shrunk to fit, which reallocates. To build
+/// Account IDs in a reused buffer, use [`AccountId::take_from_buf`] instead.
+impl TryFrom<String> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: String) -> Result<Self, Self::Error> {
+        crate::validation::validate(&account_id)?;
+        Ok(Self(account_id.into_boxed_str()))
+    }
+}
+
+impl TryFrom<Vec<u8>> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: Vec<u8>) -> Result<Self, Self::Error> {
+        crate::validation::validate_bytes(&account_id)?;
+
+        // Safety: a validated Account ID only consists of ASCII characters, which is valid UTF-8.
+        let account_id = unsafe { String::from_utf8_unchecked(account_id) };
+        Ok(Self(account_id.into_boxed_str()))
+    }
+}
+
+impl TryFrom<&[u8]> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: &[u8]) -> Result<Self, Self::Error> {
+        AccountId::from_utf8(account_id)
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<AccountId> for String {
+    fn from(account_id: AccountId) -> Self {
+        account_id.0.into_string()
+    }
+}
+
+impl From<AccountId> for Box<str> {
+    fn from(value: AccountId) -> Box<str> {
+        value.0
+    }
+}
+
+impl<'a> From<AccountId> for Cow<'a, str> {
+    fn from(value: AccountId) -> Self {
+        Cow::Owned(value.into_string())
+    }
+}
+
+impl PartialEq<AccountId> for AccountIdRef {
+    fn eq(&self, other: &AccountId) -> bool {
+        &self.0 == other.as_str()
+    }
+}
+
+impl PartialEq<AccountIdRef> for AccountId {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self.as_str() == &other.0
+    }
+}
+
+impl PartialEq<AccountId> for &AccountIdRef {
+    fn eq(&self, other: &AccountId) -> bool {
+        &self.0 == other.as_str()
+    }
+}
+
+impl<'a> PartialEq<&'a AccountIdRef> for AccountId {
+    fn eq(&self, other: &&'a AccountIdRef) -> bool {
+        self.as_str() == &other.0
+    }
+}
+
+impl PartialEq<AccountId> for String {
+    fn eq(&self, other: &AccountId) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<String> for AccountId {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<AccountId> for str {
+    fn eq(&self, other: &AccountId) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<str> for AccountId {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<AccountId> for &str {
+    fn eq(&self, other: &AccountId) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for AccountId {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<[u8]> for AccountId {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl PartialEq<AccountId> for [u8] {
+    fn eq(&self, other: &AccountId) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for AccountId {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_bytes() == *other
+    }
+}
+
+impl PartialEq<AccountId> for &[u8] {
+    fn eq(&self, other: &AccountId) -> bool {
+        *self == other.as_bytes()
+    }
+}
+
+impl PartialOrd<[u8]> for AccountId {
+    fn partial_cmp(&self, other: &[u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<AccountId> for [u8] {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> PartialOrd<&'a [u8]> for AccountId {
+    fn partial_cmp(&self, other: &&'a [u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<AccountId> for &[u8] {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other.as_bytes())
     }
 }
 
 impl PartialOrd<AccountId> for AccountIdRef {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.0.partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<AccountIdRef> for AccountId {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(&other.0)
     }
 }
 
-impl<'a> PartialOrd<AccountId> for &'a AccountIdRef {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+impl PartialOrd<AccountId> for &AccountIdRef {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.0.partial_cmp(other.as_str())
     }
 }
 
 impl<'a> PartialOrd<&'a AccountIdRef> for AccountId {
-    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(&other.0)
     }
 }
 
 impl PartialOrd<AccountId> for String {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<String> for AccountId {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other.as_str())
     }
 }
 
-impl PartialOrd<String> for AccountId {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(other.as_str())
+impl PartialEq<AccountId> for Cow<'_, str> {
+    fn eq(&self, other: &AccountId) -> bool {
+        self.as_ref() == other.as_str()
+    }
+}
+
+impl PartialEq<Cow<'_, str>> for AccountId {
+    fn eq(&self, other: &Cow<'_, str>) -> bool {
+        self.as_str() == other.as_ref()
+    }
+}
+
+impl PartialOrd<AccountId> for Cow<'_, str> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<Cow<'_, str>> for AccountId {
+    fn partial_cmp(&self, other: &Cow<'_, str>) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
+    }
+}
+
+impl PartialEq<AccountId> for Box<str> {
+    fn eq(&self, other: &AccountId) -> bool {
+        self.as_ref() == other.as_str()
+    }
+}
+
+impl PartialEq<Box<str>> for AccountId {
+    fn eq(&self, other: &Box<str>) -> bool {
+        self.as_str() == other.as_ref()
+    }
+}
+
+impl PartialOrd<AccountId> for Box<str> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<Box<str>> for AccountId {
+    fn partial_cmp(&self, other: &Box<str>) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
     }
 }
 
 impl PartialOrd<AccountId> for str {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<str> for AccountId {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other)
     }
 }
 
-impl<'a> PartialOrd<AccountId> for &'a str {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+impl PartialOrd<AccountId> for &str {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.partial_cmp(&other.as_str())
     }
 }
 
 impl<'a> PartialOrd<&'a str> for AccountId {
-    fn partial_cmp(&self, other: &&'a str) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(*other)
     }
 }
@@ -320,6 +1136,25 @@ impl<'a> From<&'a AccountId> for Cow<'a, AccountIdRef> {
     }
 }
 
+/// Borrows an [`AccountId`] as an [`AccountIdRef`], without allocating.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{AccountId, AccountIdRef};
+///
+/// let vec_of_account_ids: Vec<AccountId> =
+///     vec!["alice.near".parse().unwrap(), "bob.near".parse().unwrap()];
+///
+/// let refs = vec_of_account_ids.iter().map(Into::into).collect::<Vec<&AccountIdRef>>();
+/// assert_eq!(refs, ["alice.near", "bob.near"]);
+/// ```
+impl<'a> From<&'a AccountId> for &'a AccountIdRef {
+    fn from(value: &'a AccountId) -> Self {
+        value.as_account_id_ref()
+    }
+}
+
 impl<'a> From<Cow<'a, AccountIdRef>> for AccountId {
     fn from(value: Cow<'a, AccountIdRef>) -> Self {
         value.into_owned()
@@ -341,11 +1176,777 @@ impl<'a> arbitrary::Arbitrary<'a> for AccountId {
     }
 }
 
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for AccountId {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        // Labels are capped at 13 characters and named accounts at 4 labels, so that named
+        // accounts never exceed `MAX_LEN` and only single-character ones need to be filtered out.
+        let label = "[a-z0-9]{1,6}([_-][a-z0-9]{1,6})?";
+        let named = proptest::collection::vec(label, 1..=4)
+            .prop_map(|labels| labels.join("."))
+            .prop_filter("the Account ID is too short", |account_id| {
+                account_id.len() >= AccountId::MIN_LEN
+            });
+
+        // Named accounts come first, so that shrinking moves towards them.
+        prop_oneof![
+            6 => named,
+            2 => "[0-9a-f]{64}",
+            1 => "0x[0-9a-f]{40}",
+            1 => "0s[0-9a-f]{40}",
+        ]
+        .prop_map(|account_id| AccountId(account_id.into_boxed_str()))
+        .boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    use crate::count_alloc::count_allocs;
+
+    #[test]
+    fn test_no_unnecessary_clone() {
+        let account_id: AccountId = "alice.near".parse().unwrap();
+        let (_, allocs) = count_allocs(|| String::from(account_id.clone()));
+        assert_eq!(allocs, 1, "only the clone itself should allocate");
+
+        let account_id: AccountId = "alice.near".parse().unwrap();
+        let (string, allocs) = count_allocs(|| String::from(account_id));
+        assert_eq!(allocs, 0);
+
+        let (account_id, allocs) = count_allocs(|| AccountId::try_from(string).unwrap());
+        assert_eq!(allocs, 0);
+
+        let (boxed, allocs) = count_allocs(|| Box::<str>::from(account_id));
+        assert_eq!(allocs, 0);
+
+        let account_id = AccountId::try_from(boxed).unwrap();
+        let ptr = account_id.as_bytes().as_ptr();
+        let (string, allocs) = count_allocs(|| account_id.into_string());
+        assert_eq!(allocs, 0);
+        assert_eq!(string.as_ptr(), ptr);
+
+        let account_id = AccountId::try_from(string).unwrap();
+        let (boxed, allocs) = count_allocs(|| account_id.into_boxed_str());
+        assert_eq!(allocs, 0);
+        assert_eq!(boxed.as_ptr(), ptr);
+
+        let (account_id, allocs) = count_allocs(|| AccountId::try_from(boxed).unwrap());
+        assert_eq!(allocs, 0);
+
+        let (account_id, allocs) =
+            count_allocs(|| AccountId::from(Cow::<AccountIdRef>::from(account_id)));
+        assert_eq!(allocs, 0);
+
+        let (_, allocs) = count_allocs(|| AccountId::from(Cow::from(&account_id)));
+        assert_eq!(allocs, 1, "a borrowed `Cow` has to be cloned");
+
+        let ptr = account_id.as_bytes().as_ptr();
+        let (cow, allocs) = count_allocs(|| Cow::<'static, str>::from(account_id));
+        assert_eq!(allocs, 0);
+        assert!(matches!(cow, Cow::Owned(_)));
+        assert_eq!(cow.as_ptr(), ptr);
+        assert_eq!(cow, "alice.near");
+    }
+
+    #[test]
+    fn test_bytes_comparisons() {
+        use core::cmp::Ordering;
+
+        let alice: AccountId = "alice.near".parse().unwrap();
+
+        let bytes: &[u8] = b"alice.near";
+        assert_eq!(alice, *bytes);
+        assert_eq!(*bytes, alice);
+        assert_eq!(alice, bytes);
+        assert_eq!(bytes, alice);
+        assert_eq!(alice.partial_cmp(bytes), Some(Ordering::Equal));
+
+        for (bytes, ordering) in [
+            (&b"bob.near"[..], Ordering::Less),
+            (b"alice.near\xff", Ordering::Less),
+            (b"alice.nea", Ordering::Greater),
+        ] {
+            assert_ne!(alice, *bytes);
+            assert_ne!(*bytes, alice);
+            assert_ne!(alice, bytes);
+            assert_ne!(bytes, alice);
+            assert_eq!(alice.partial_cmp(bytes), Some(ordering));
+            assert_eq!(alice.partial_cmp(&bytes), Some(ordering));
+            assert_eq!(bytes.partial_cmp(&alice), Some(ordering.reverse()));
+            assert_eq!((*bytes).partial_cmp(&alice), Some(ordering.reverse()));
+        }
+    }
+
+    #[test]
+    fn test_cow_str_comparisons() {
+        let alice: AccountId = "alice.near".parse().unwrap();
+
+        for cow in [
+            Cow::Borrowed("alice.near"),
+            Cow::Owned("alice.near".to_string()),
+        ] {
+            assert_eq!(alice, cow);
+            assert_eq!(cow, alice);
+            assert_eq!(alice.partial_cmp(&cow), Some(core::cmp::Ordering::Equal));
+        }
+
+        for cow in [
+            Cow::Borrowed("bob.near"),
+            Cow::Owned("bob.near".to_string()),
+        ] {
+            assert_ne!(alice, cow);
+            assert_ne!(cow, alice);
+            assert!(alice < cow);
+            assert!(cow > alice);
+        }
+    }
+
+    #[test]
+    fn test_boxed_str_comparisons() {
+        let alice: AccountId = "alice.near".parse().unwrap();
+
+        let boxed = Box::<str>::from("alice.near");
+        assert_eq!(alice, boxed);
+        assert_eq!(boxed, alice);
+        assert_eq!(alice.partial_cmp(&boxed), Some(core::cmp::Ordering::Equal));
+
+        let boxed = Box::<str>::from("bob.near");
+        assert_ne!(alice, boxed);
+        assert_ne!(boxed, alice);
+        assert!(alice < boxed);
+        assert!(boxed > alice);
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let bytes = b"alice.near".to_vec();
+        let ptr = bytes.as_ptr();
+        let (account_id, allocs) = count_allocs(|| AccountId::try_from(bytes).unwrap());
+        assert_eq!(allocs, 0);
+        assert_eq!(
+            account_id.as_bytes().as_ptr(),
+            ptr,
+            "the buffer should be reused"
+        );
+        assert_eq!(account_id, "alice.near");
+
+        assert_eq!(
+            AccountId::try_from(b"alice.near".as_slice()).unwrap(),
+            "alice.near"
+        );
+        assert_eq!(
+            <&AccountIdRef>::try_from(b"alice.near".as_slice()).unwrap(),
+            "alice.near"
+        );
+
+        for bytes in [
+            &b"alice\xff.near"[..],
+            b"\xc3\x28",
+            "ƒelicia.near".as_bytes(),
+        ] {
+            let err = AccountId::try_from(bytes.to_vec()).unwrap_err();
+            assert_eq!(err.kind(), &crate::ParseErrorKind::InvalidChar);
+            assert_eq!(AccountId::try_from(bytes).unwrap_err(), err);
+            assert_eq!(<&AccountIdRef>::try_from(bytes).unwrap_err(), err);
+        }
+
+        let err = AccountId::try_from(b"alice..near".to_vec()).unwrap_err();
+        assert_eq!(err.kind(), &crate::ParseErrorKind::RedundantSeparator);
+    }
+
+    #[test]
+    fn test_try_from_chars() {
+        use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
+
+        for account_id in OK_ACCOUNT_IDS {
+            assert_eq!(
+                AccountId::try_from_chars(account_id.chars()),
+                account_id.parse::<AccountId>(),
+                "{:?}",
+                account_id
+            );
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            let mut validator = crate::Validator::new();
+            let expected = validator
+                .push(account_id.as_bytes())
+                .and_then(|()| validator.finish());
+            assert_eq!(
+                AccountId::try_from_chars(account_id.chars()).map(|_| ()),
+                expected,
+                "{:?}",
+                account_id
+            );
+            assert!(expected.is_err(), "{:?}", account_id);
+        }
+
+        let mut read = 0;
+        let stream = (0..10_000).map(|_| 'a').inspect(|_| read += 1);
+        let err = AccountId::try_from_chars(stream).unwrap_err();
+        assert_eq!(err.kind(), &crate::ParseErrorKind::TooLong);
+        assert_eq!(read, AccountId::MAX_LEN + 1);
+
+        // Reading stops at the first invalid character, and reports it in full.
+        let mut read = 0;
+        let stream = "alice.\u{192}".chars().chain(core::iter::repeat('a'));
+        let err = AccountId::try_from_chars(stream.inspect(|_| read += 1)).unwrap_err();
+        assert_eq!(err.kind(), &crate::ParseErrorKind::InvalidChar);
+        assert_eq!(err.char, Some((6, '\u{192}')));
+        assert_eq!(read, 7);
+
+        let mut read = 0;
+        let stream = "alice..".chars().chain(core::iter::repeat('a'));
+        let err = AccountId::try_from_chars(stream.inspect(|_| read += 1)).unwrap_err();
+        assert_eq!(err.kind(), &crate::ParseErrorKind::RedundantSeparator);
+        assert_eq!(read, 7);
+
+        let (_, allocs) = count_allocs(|| AccountId::try_from_chars("alice.near".chars()).unwrap());
+        assert_eq!(allocs, 1);
+    }
+
+    #[test]
+    fn test_take_from_buf() {
+        let mut buf = String::with_capacity(64);
+        buf.push_str("alice.near");
+        let capacity = buf.capacity();
+
+        let (account_id, allocs) = count_allocs(|| AccountId::take_from_buf(&mut buf).unwrap());
+        assert_eq!(allocs, 1);
+        assert_eq!(account_id, "alice.near");
+        assert!(buf.is_empty());
+        assert_eq!(buf.capacity(), capacity);
+
+        buf.push_str("alice..near");
+        let err = AccountId::take_from_buf(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), &crate::ParseErrorKind::RedundantSeparator);
+        assert_eq!(buf, "alice..near");
+    }
+
+    #[test]
+    fn test_no_unnecessary_clone_on_borrow() {
+        let account_id: AccountId = "alice.near".parse().unwrap();
+
+        let (_, allocs) = count_allocs(|| {
+            let account_id_ref: &AccountIdRef = &account_id;
+            let _: &str = account_id_ref.as_ref();
+            AccountIdRef::new(account_id.as_str()).unwrap();
+            AccountId::validate(account_id.as_str()).unwrap();
+        });
+        assert_eq!(allocs, 0);
+
+        let (_, allocs) = count_allocs(|| "alice.near".parse::<AccountId>().unwrap());
+        assert_eq!(allocs, 1);
+    }
+
+    #[test]
+    fn test_hash_consistent_with_borrow() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+        use std::hash::{Hash, Hasher};
+
+        fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let owned: AccountId = account_id.parse().unwrap();
+            let borrowed = AccountIdRef::new_or_panic(account_id);
+
+            assert_eq!(hash(&owned), hash(account_id));
+            assert_eq!(hash(borrowed), hash(account_id));
+        }
+
+        let map: HashMap<AccountId, u32> = [("alice.near", 1), ("bob.near", 2)]
+            .iter()
+            .map(|(account_id, value)| (account_id.parse().unwrap(), *value))
+            .collect();
+        assert_eq!(map.get("alice.near"), Some(&1));
+        assert_eq!(map.get(AccountIdRef::new_or_panic("bob.near")), Some(&2));
+        assert_eq!(map.get("carol.near"), None);
+    }
+
+    #[test]
+    fn test_btree_set_lookup() {
+        use std::ops::Bound;
+
+        let set: std::collections::BTreeSet<AccountId> = ["alice.near", "bob.near", "near"]
+            .iter()
+            .map(|account_id| account_id.parse().unwrap())
+            .collect();
+
+        assert!(set.contains(AccountIdRef::new_or_panic("alice.near")));
+        assert!(!set.contains(AccountIdRef::new_or_panic("carol.near")));
+
+        assert!(set.contains("bob.near"));
+        assert!(!set.contains("carol.near"));
+
+        let range: Vec<&AccountId> = set
+            .range::<str, _>((Bound::Included("b"), Bound::Excluded("z")))
+            .collect();
+        assert_eq!(range, ["bob.near", "near"]);
+    }
+
+    #[test]
+    fn test_system() {
+        assert_eq!(AccountId::system(), "system");
+        assert_eq!(AccountId::system(), AccountIdRef::SYSTEM);
+        assert!(AccountIdRef::SYSTEM.is_system());
+        assert!(!AccountIdRef::SYSTEM.is_top_level());
+        assert!(!"system.near".parse::<AccountId>().unwrap().is_system());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_random() {
+        use rand::{rngs::SmallRng, SeedableRng};
+
+        use crate::AccountType;
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut max_lens = [false; AccountId::MAX_LEN + 1];
+        for max_len in (0..=AccountId::MAX_LEN + 1).cycle().take(10_000) {
+            let account_id = AccountId::random_named(&mut rng, max_len);
+            assert!(
+                AccountId::validate(account_id.as_str()).is_ok(),
+                "{}",
+                account_id
+            );
+            assert_eq!(account_id.get_account_type(), AccountType::NamedAccount);
+            assert!(account_id.len() <= max_len.max(AccountId::MIN_LEN));
+            max_lens[account_id.len()] = true;
+        }
+        assert!(max_lens[AccountId::MIN_LEN..].iter().all(|&seen| seen));
+
+        let mut account_types = [false; 3];
+        for _ in 0..100 {
+            let account_id = AccountId::random_implicit(&mut rng);
+            assert!(
+                AccountId::validate(account_id.as_str()).is_ok(),
+                "{}",
+                account_id
+            );
+            match account_id.get_account_type() {
+                AccountType::NearImplicitAccount => account_types[0] = true,
+                AccountType::EthImplicitAccount => account_types[1] = true,
+                AccountType::NearDeterministicAccount => account_types[2] = true,
+                AccountType::NamedAccount => panic!("{} is a named account", account_id),
+            }
+        }
+        assert_eq!(account_types, [true; 3]);
+    }
+
+    #[test]
+    fn test_as_account_id_ref() {
+        fn generic<T: AsRef<str> + AsRef<AccountIdRef>>(account_id: &T) -> &str {
+            AsRef::<AccountIdRef>::as_ref(account_id).as_str()
+        }
+
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let alice_ref = alice.as_account_id_ref();
+        assert_eq!(alice_ref, "alice.near");
+        assert_eq!(alice_ref.as_str().as_ptr(), alice.as_str().as_ptr());
+        assert_eq!(alice_ref.as_str(), generic(&alice));
+    }
+
+    #[test]
+    fn test_from_account_id_for_account_id_ref() {
+        let account_ids: Vec<AccountId> = crate::test_data::OK_ACCOUNT_IDS
+            .iter()
+            .map(|account_id| account_id.parse().unwrap())
+            .collect();
+
+        let (refs, allocs) = count_allocs(|| {
+            account_ids
+                .iter()
+                .map(Into::into)
+                .collect::<Vec<&AccountIdRef>>()
+        });
+        assert_eq!(allocs, 1);
+        for (account_id, account_id_ref) in account_ids.iter().zip(refs) {
+            assert_eq!(
+                account_id_ref.as_str().as_ptr(),
+                account_id.as_str().as_ptr()
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_const_result() {
+        const _: () = assert!(AccountId::validate_const_result("alice.near").is_ok());
+
+        for account_id in crate::test_data::OK_ACCOUNT_IDS
+            .into_iter()
+            .chain(crate::test_data::BAD_ACCOUNT_IDS)
+        {
+            assert_eq!(
+                AccountId::validate_const_result(account_id),
+                AccountId::validate(account_id).map_err(|err| err.kind),
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS
+            .into_iter()
+            .chain(crate::test_data::BAD_ACCOUNT_IDS)
+        {
+            assert_eq!(
+                AccountId::parse(account_id),
+                account_id.parse::<AccountId>(),
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let account_ids = AccountId::parse_many(crate::test_data::OK_ACCOUNT_IDS).unwrap();
+        assert_eq!(account_ids, crate::test_data::OK_ACCOUNT_IDS);
+
+        let owned = vec![String::from("alice.near"), String::from("bob.near")];
+        assert_eq!(
+            AccountId::parse_many(&owned).unwrap(),
+            ["alice.near", "bob.near"]
+        );
+
+        assert_eq!(AccountId::parse_many(Vec::<&str>::new()), Ok(vec![]));
+
+        let (idx, err) =
+            AccountId::parse_many(["alice.near", "bob.near", "carol..near", "Dave.near"])
+                .unwrap_err();
+        assert_eq!(idx, 2);
+        assert_eq!(err.kind(), &crate::ParseErrorKind::RedundantSeparator);
+    }
+
+    #[test]
+    fn test_from_labels() {
+        assert_eq!(
+            AccountId::from_labels(vec![
+                "app".to_string(),
+                "alice".to_string(),
+                "near".to_string()
+            ])
+            .unwrap(),
+            "app.alice.near"
+        );
+        assert_eq!(AccountId::from_labels(["near"]).unwrap(), "near");
+
+        let long_label = "a".repeat(AccountId::MAX_LEN - "near".len());
+        let cases = [
+            (vec![], crate::ParseErrorKind::TooShort, None),
+            (vec!["a"], crate::ParseErrorKind::TooShort, None),
+            (
+                vec![&long_label, "near"],
+                crate::ParseErrorKind::TooLong,
+                None,
+            ),
+            (
+                vec!["app", "", "near"],
+                crate::ParseErrorKind::RedundantSeparator,
+                Some((4, '.')),
+            ),
+            (
+                vec!["app", "alice-", "near"],
+                crate::ParseErrorKind::RedundantSeparator,
+                Some((10, '.')),
+            ),
+            (
+                vec!["app", "Alice", "near"],
+                crate::ParseErrorKind::InvalidChar,
+                Some((4, 'A')),
+            ),
+            (
+                vec!["app", "alice.near"],
+                crate::ParseErrorKind::InvalidChar,
+                Some((9, '.')),
+            ),
+        ];
+        for (labels, kind, char) in cases {
+            assert_eq!(
+                AccountId::from_labels(&labels),
+                Err(ParseAccountError { kind, char }),
+                "{:?}",
+                labels
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_lossy() {
+        let cases = [
+            ("alice.near", "alice.near", false),
+            ("Alice.near", "alice.near", true),
+            ("ALICE.NEAR", "alice.near", true),
+            ("App_1.Alice-B.near", "app_1.alice-b.near", true),
+        ];
+        for (input, expected, changed) in cases {
+            let (account_id, was_changed) = AccountId::parse_lossy(input).unwrap();
+            assert_eq!(account_id, expected);
+            assert_eq!(was_changed, changed, "{:?}", input);
+        }
+
+        let cases = [
+            (
+                "ƒelicia.near",
+                crate::ParseErrorKind::InvalidChar,
+                Some((0, 'ƒ')),
+            ),
+            (
+                "Ƒelicia.near",
+                crate::ParseErrorKind::InvalidChar,
+                Some((0, 'Ƒ')),
+            ),
+            (
+                "Alice.Ñear",
+                crate::ParseErrorKind::InvalidChar,
+                Some((6, 'Ñ')),
+            ),
+            (
+                "Alice..near",
+                crate::ParseErrorKind::RedundantSeparator,
+                Some((6, '.')),
+            ),
+            ("A", crate::ParseErrorKind::TooShort, None),
+        ];
+        for (input, kind, char) in cases {
+            assert_eq!(
+                AccountId::parse_lossy(input),
+                Err(ParseAccountError { kind, char }),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_allowing_hex_case() {
+        let ok_cases = [
+            (
+                "0xB794F5eA0ba39494cE839613fffBA74279579268",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            (
+                "0XB794F5EA0BA39494CE839613FFFBA74279579268",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            (
+                "98793CD91A3F870FB126F66285808C7E094AFCFC4EDA8A970F6648CDF0DBD6DE",
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+            ),
+            ("alice.near", "alice.near"),
+        ];
+        for (input, expected) in ok_cases {
+            assert_eq!(
+                AccountId::parse_allowing_hex_case(input).unwrap(),
+                expected,
+                "{:?}",
+                input
+            );
+        }
+
+        let err_cases = [
+            "Alice.near",
+            "App.Alice.near",
+            // Too short to be an ETH-implicit account.
+            "0xB794F5eA0ba39494cE839613fffBA7427957926",
+            // Not hex.
+            "0xG794F5eA0ba39494cE839613fffBA74279579268",
+            // Only hex implicit formats are case-folded.
+            "0SB794F5eA0ba39494cE839613fffBA74279579268",
+        ];
+        for input in err_cases {
+            assert_eq!(
+                AccountId::parse_allowing_hex_case(input),
+                input.parse::<AccountId>(),
+                "{:?}",
+                input
+            );
+            assert!(AccountId::parse_allowing_hex_case(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_or_suggest() {
+        assert_eq!(
+            AccountId::parse_or_suggest("alice.near").unwrap(),
+            "alice.near"
+        );
+
+        let cases = [
+            ("Alice.Near", "alice.near"),
+            ("alice..near", "alice.near"),
+            ("not-_alice.near", "not-alice.near"),
+            ("_alice.near.", "alice.near"),
+            (" bob.near\n", "bob.near"),
+            ("..Bob__Near-", "bob_near"),
+        ];
+        for (input, expected) in cases {
+            let (err, suggestion) = AccountId::parse_or_suggest(input).unwrap_err();
+            assert_eq!(err, input.parse::<AccountId>().unwrap_err());
+            assert_eq!(
+                suggestion.as_deref(),
+                Some(AccountIdRef::new_or_panic(expected))
+            );
+        }
+
+        for input in [
+            "alice@near",
+            "ƒelicia.near",
+            "a",
+            "..",
+            "A".repeat(65).as_str(),
+        ] {
+            let (err, suggestion) = AccountId::parse_or_suggest(input).unwrap_err();
+            assert_eq!(err, input.parse::<AccountId>().unwrap_err());
+            assert_eq!(suggestion, None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_rebase_tla() {
+        let long_prefix = "a".repeat(AccountId::MAX_LEN - ".near".len());
+        let account_ids: Vec<AccountId> = [
+            "alice.near",
+            "app.alice.near",
+            "near",
+            "alice.testnet",
+            "alicenear",
+            "alice.near.testnet",
+            &format!("{}.near", long_prefix),
+        ]
+        .iter()
+        .map(|account_id| account_id.parse().unwrap())
+        .collect();
+
+        let rebased = AccountId::rebase_tla(
+            &account_ids,
+            AccountIdRef::new_or_panic("near"),
+            AccountIdRef::new_or_panic("testnet"),
+        );
+        let rebased: Vec<_> = rebased
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|result| {
+                result
+                    .as_ref()
+                    .map(|account_id| account_id.as_str())
+                    .map_err(|err| err.kind())
+            })
+            .collect();
+        assert_eq!(
+            rebased,
+            [
+                Ok("alice.testnet"),
+                Ok("app.alice.testnet"),
+                Ok("testnet"),
+                Ok("alice.testnet"),
+                Ok("alicenear"),
+                Ok("alice.near.testnet"),
+                Err(&crate::ParseErrorKind::TooLong),
+            ]
+        );
+
+        let near = AccountIdRef::new_or_panic("near");
+        for not_top_level in ["alice.near", "app.testnet", "system"] {
+            let not_top_level = AccountIdRef::new_or_panic(not_top_level);
+            assert_eq!(
+                AccountId::rebase_tla(&account_ids, not_top_level, near),
+                Err(crate::NotTopLevelAccount(())),
+                "{:?}",
+                not_top_level
+            );
+            assert_eq!(
+                AccountId::rebase_tla(&account_ids, near, not_top_level),
+                Err(crate::NotTopLevelAccount(())),
+                "{:?}",
+                not_top_level
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_near_implicit() {
+        for public_key in [[0; 32], [0xff; 32], std::array::from_fn(|i| i as u8)] {
+            let account_id = AccountId::from_near_implicit(&public_key);
+            assert_eq!(AccountId::validate(account_id.as_str()), Ok(()));
+            assert!(account_id.get_account_type() == crate::AccountType::NearImplicitAccount);
+        }
+
+        assert_eq!(
+            AccountId::from_near_implicit(&std::array::from_fn(|i| i as u8)),
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "borsh"))]
+    fn test_ordering_stable_across_round_trips() {
+        let mut account_ids: Vec<AccountId> = [
+            "near",
+            "a.near",
+            "alice.near",
+            "alice-1.near",
+            "alice_1.near",
+            "alice.a.near",
+            "aa",
+            "a-a",
+            "a_a",
+            "a.a",
+            "00",
+            "0x00",
+            "zz",
+            "system",
+        ]
+        .iter()
+        .map(|account_id| account_id.parse().unwrap())
+        .collect();
+        account_ids.sort();
+
+        let mut strings: Vec<&str> = account_ids
+            .iter()
+            .map(|account_id| account_id.as_str())
+            .collect();
+        strings.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        assert!(account_ids
+            .iter()
+            .map(|account_id| account_id.as_str())
+            .eq(strings));
+
+        let mut via_serde: Vec<AccountId> = account_ids
+            .iter()
+            .map(|account_id| {
+                serde_json::from_value(serde_json::to_value(account_id).unwrap()).unwrap()
+            })
+            .collect();
+        let mut via_borsh: Vec<AccountId> = account_ids
+            .iter()
+            .map(|account_id| borsh::from_slice(&borsh::to_vec(account_id).unwrap()).unwrap())
+            .collect();
+        via_serde.reverse();
+        via_serde.sort();
+        via_borsh.reverse();
+        via_borsh.sort();
+
+        assert_eq!(via_serde, account_ids);
+        assert_eq!(via_borsh, account_ids);
+    }
+
     #[test]
     #[cfg(feature = "arbitrary")]
     fn test_arbitrary() {
@@ -373,6 +1974,44 @@ mod tests {
             );
         }
     }
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_proptest_generates_valid_account_ids(account_id: AccountId) {
+            proptest::prop_assert!(AccountId::validate(account_id.as_str()).is_ok());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn test_proptest_shrinking() {
+        use proptest::{strategy::Strategy, test_runner::TestRunner};
+
+        let mut runner = TestRunner::deterministic();
+        let strategy = proptest::arbitrary::any::<AccountId>();
+
+        let mut account_types = std::collections::HashSet::new();
+        for _ in 0..256 {
+            let account_id = strategy.new_tree(&mut runner).unwrap().current();
+            account_types.insert(account_id.get_account_type());
+        }
+        assert_eq!(account_types.len(), 4);
+
+        let err = runner
+            .run(&strategy, |account_id| {
+                proptest::prop_assert!(account_id.len() > 64);
+                Ok(())
+            })
+            .unwrap_err();
+        match err {
+            proptest::test_runner::TestError::Fail(_, minimal) => {
+                assert_eq!(minimal.get_account_type(), crate::AccountType::NamedAccount);
+                assert_eq!(minimal.len(), AccountId::MIN_LEN);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
     #[test]
     #[cfg(feature = "schemars")]
     fn test_schemars() {
@@ -383,9 +2022,12 @@ mod tests {
             json_schema,
             serde_json::json!({
                     "$schema": "http://json-schema.org/draft-07/schema#",
-                    "description": "NEAR Account Identifier.\n\nThis is a unique, syntactically valid, human-readable account identifier on the NEAR network.\n\n[See the crate-level docs for information about validation.](index.html#account-id-rules)\n\nAlso see [Error kind precedence](AccountId#error-kind-precedence).\n\n## Examples\n\n``` use near_account_id::AccountId;\n\nlet alice: AccountId = \"alice.near\".parse().unwrap();\n\nassert!(\"ƒelicia.near\".parse::<AccountId>().is_err()); // (ƒ is not f) ```",
+                    "description": "NEAR Account Identifier.\n\nThis is a unique, syntactically valid, human-readable account identifier on the NEAR network.\n\n[See the crate-level docs for information about validation.](index.html#account-id-rules)\n\nAlso see [Error kind precedence](AccountId#error-kind-precedence).\n\nAccount IDs are ordered byte-lexicographically by their string representation, which is exactly what they serialize to. This ordering is stable and survives serialization round-trips.\n\n## Examples\n\n``` use near_account_id::AccountId;\n\nlet alice: AccountId = \"alice.near\".parse().unwrap();\n\nassert!(\"ƒelicia.near\".parse::<AccountId>().is_err()); // (ƒ is not f) ```",
                     "title": "AccountId",
-                    "type": "string"
+                    "type": "string",
+                    "minLength": 2,
+                    "maxLength": 64,
+                    "pattern": "^(([a-z\\d]+[-_])*[a-z\\d]+\\.)*([a-z\\d]+[-_])*[a-z\\d]+$"
                 }
             )
         );
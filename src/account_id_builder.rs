@@ -0,0 +1,177 @@
+use alloc::string::String;
+
+use crate::{AccountId, ParseAccountError, ParseErrorKind};
+
+/// Builds an [`AccountId`] label by label, validating it once at the end.
+///
+/// Labels are joined with `.` in the order they are pushed, so the top-level account comes
+/// last. Only the length is checked while pushing, so that overly long input is rejected
+/// early: once the Account ID would exceed [`AccountId::MAX_LEN`], no more labels are appended.
+/// Everything else is validated by [`AccountIdBuilder::build`].
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{AccountIdBuilder, ParseErrorKind};
+///
+/// let mut builder = AccountIdBuilder::new();
+/// builder.push_label("app").unwrap();
+/// builder.push_label("alice").unwrap();
+/// builder.push_label("near").unwrap();
+/// assert_eq!(builder.build().unwrap(), "app.alice.near");
+///
+/// let mut builder = AccountIdBuilder::new();
+/// builder.push_label("Alice").unwrap();
+/// builder.push_label("near").unwrap();
+/// assert_eq!(builder.build().unwrap_err().kind(), &ParseErrorKind::InvalidChar);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AccountIdBuilder {
+    account_id: String,
+    labels: usize,
+    nested_separator: Option<usize>,
+    too_long: bool,
+}
+
+impl AccountIdBuilder {
+    /// Creates a builder without any labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a label, after a `.` unless it's the first one.
+    ///
+    /// Returns [`ParseErrorKind::TooLong`] if the Account ID would get longer than
+    /// [`AccountId::MAX_LEN`]. The label is not appended then, and neither is any later label,
+    /// and [`AccountIdBuilder::build`] fails with the same error.
+    pub fn push_label(&mut self, label: &str) -> Result<(), ParseAccountError> {
+        let separator = usize::from(self.labels > 0);
+        if self.too_long || self.account_id.len() + separator + label.len() > AccountId::MAX_LEN {
+            self.too_long = true;
+            return Err(Self::too_long_error());
+        }
+
+        if separator > 0 {
+            self.account_id.push('.');
+        }
+        if let (None, Some(idx)) = (self.nested_separator, label.find('.')) {
+            self.nested_separator = Some(self.account_id.len() + idx);
+        }
+        self.account_id.push_str(label);
+        self.labels += 1;
+        Ok(())
+    }
+
+    /// Validates the joined labels as an Account ID.
+    ///
+    /// Errors report positions in the joined Account ID. A label that contains a `.` itself is
+    /// reported as [`ParseErrorKind::InvalidChar`].
+    pub fn build(self) -> Result<AccountId, ParseAccountError> {
+        if self.too_long {
+            return Err(Self::too_long_error());
+        }
+        crate::validation::validate(&self.account_id)?;
+        if let Some(idx) = self.nested_separator {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::InvalidChar,
+                char: Some((idx, '.')),
+            });
+        }
+        Ok(AccountId(self.account_id.into_boxed_str()))
+    }
+
+    fn too_long_error() -> ParseAccountError {
+        ParseAccountError {
+            kind: ParseErrorKind::TooLong,
+            char: None,
+        }
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for AccountIdBuilder {
+    /// Appends every label, deferring errors to [`AccountIdBuilder::build`].
+    ///
+    /// Stops consuming `labels` once the Account ID gets too long.
+    fn extend<I: IntoIterator<Item = S>>(&mut self, labels: I) {
+        for label in labels {
+            // A too long Account ID is reported again by `build`.
+            if self.push_label(label.as_ref()).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let mut builder = AccountIdBuilder::new();
+        builder.extend(["app", "alice", "near"]);
+        assert_eq!(builder.build().unwrap(), "app.alice.near");
+
+        let cases = [
+            (&["app", "", "near"][..], ParseErrorKind::RedundantSeparator),
+            (&["app.v2", "near"], ParseErrorKind::InvalidChar),
+            (&["App", "near"], ParseErrorKind::InvalidChar),
+            (&["a"], ParseErrorKind::TooShort),
+            (&[], ParseErrorKind::TooShort),
+        ];
+        for (labels, kind) in cases {
+            let mut builder = AccountIdBuilder::new();
+            for label in labels {
+                builder.push_label(label).unwrap();
+            }
+            assert_eq!(builder.build().unwrap_err().kind(), &kind, "{:?}", labels);
+        }
+    }
+
+    #[test]
+    fn test_too_long() {
+        let label = "a".repeat(30);
+
+        let mut builder = AccountIdBuilder::new();
+        builder.push_label(&label).unwrap();
+        builder.push_label(&label).unwrap();
+        let err = builder.push_label("near").unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+        assert_eq!(builder.build().unwrap_err(), err);
+
+        let mut builder = AccountIdBuilder::new();
+        builder.extend([&label, &label, "near"]);
+        assert_eq!(builder.build().unwrap_err(), err);
+
+        let mut builder = AccountIdBuilder::new();
+        builder.push_label(&label).unwrap();
+        builder
+            .push_label(&"b".repeat(AccountId::MAX_LEN - 31))
+            .unwrap();
+        assert_eq!(builder.build().unwrap().len(), AccountId::MAX_LEN);
+
+        // Nothing is appended after the limit is hit, even if a later label would still fit.
+        let mut builder = AccountIdBuilder::new();
+        builder.push_label(&label).unwrap();
+        assert!(builder.push_label(&"b".repeat(AccountId::MAX_LEN)).is_err());
+        assert!(builder.push_label("near").is_err());
+        assert_eq!(builder.account_id, label);
+        assert_eq!(builder.build().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_extend_stops_early() {
+        let mut consumed = 0;
+        let labels = (0..1000).map(|_| "a").inspect(|_| consumed += 1);
+
+        let mut builder = AccountIdBuilder::new();
+        builder.extend(labels);
+        assert_eq!(builder.account_id.len(), AccountId::MAX_LEN - 1);
+        assert_eq!(
+            builder.build().unwrap_err().kind(),
+            &ParseErrorKind::TooLong
+        );
+        // 32 labels of `a` fit, the 33rd is rejected and nothing after it is consumed.
+        assert_eq!(consumed, 33);
+    }
+}
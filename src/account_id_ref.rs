@@ -1,6 +1,14 @@
-use std::borrow::Cow;
-
-use crate::{AccountId, ParseAccountError};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+};
+use core::ops::RangeInclusive;
+
+use crate::{
+    AccountId, ParseAccountError, ParseAccountTypeError, ParseErrorKind, RegistrationError,
+    TooManyLabels,
+};
 
 /// Account identifier. This is the human readable UTF-8 string which is used internally to index
 /// accounts on the network and their respective state.
@@ -28,32 +36,107 @@ use crate::{AccountId, ParseAccountError};
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Hash)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
-pub struct AccountIdRef(pub(crate) str);
+pub struct AccountIdRef(
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(length(min = 2, max = 64), regex(path = "crate::ACCOUNT_ID_PATTERN"))
+    )]
+    pub(crate) str,
+);
 
 /// Enum representing possible types of accounts.
 /// This `enum` is returned by the [`get_account_type`] method on [`AccountIdRef`].
 /// See its documentation for more.
 ///
+/// Account types are ordered by their declaration order, i.e. named accounts come first,
+/// followed by the implicit kinds.
+///
+/// With the `serde` feature, account types are (de)serialized as their [canonical names](AccountType::as_str).
+///
+/// New kinds of accounts may be added by the protocol, so matches on `AccountType` need a
+/// wildcard arm.
+///
 /// [`get_account_type`]: AccountIdRef::get_account_type
 /// [`AccountIdRef`]: struct.AccountIdRef.html
-#[derive(PartialEq)]
+#[non_exhaustive]
+#[derive(Eq, Ord, Hash, Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub enum AccountType {
-    /// Any valid account, that is neither NEAR-implicit nor ETH-implicit.
+    /// Any valid account, that is neither NEAR-implicit, ETH-implicit nor NEAR-deterministic.
     NamedAccount,
     /// An account with 64 characters long hexadecimal address.
     NearImplicitAccount,
     /// An account which address starts with '0x', followed by 40 hex characters.
     EthImplicitAccount,
+    /// An account which address starts with '0s', followed by 40 hex characters.
+    ///
+    /// NEAR-deterministic accounts are a distinct kind of account, derived from the hash of their
+    /// initial state rather than a public key. Like implicit accounts, they cannot be created as
+    /// sub-accounts of a named account, so they are considered implicit by [`AccountType::is_implicit`].
+    NearDeterministicAccount,
+}
+
+/// A token of an account ID, either a label or a separator.
+/// This `enum` is returned by the [`tokens`] method on [`AccountIdRef`].
+///
+/// [`tokens`]: AccountIdRef::tokens
+#[derive(Eq, Hash, Clone, Copy, Debug, PartialEq)]
+pub enum Token<'a> {
+    /// A run of lowercase alphanumeric characters.
+    Label(&'a str),
+    /// One of the separators `-`, `_` or `.`.
+    Separator(char),
 }
 
 impl AccountType {
+    /// Returns `true` for every account type, except [`AccountType::NamedAccount`].
     pub fn is_implicit(&self) -> bool {
         match &self {
             Self::NearImplicitAccount => true,
             Self::EthImplicitAccount => true,
+            Self::NearDeterministicAccount => true,
             Self::NamedAccount => false,
         }
     }
+
+    /// Returns the canonical name of the account type, as used by its [`Display`](core::fmt::Display)
+    /// and [`FromStr`](core::str::FromStr) implementations.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountType;
+    ///
+    /// assert_eq!(AccountType::NearImplicitAccount.as_str(), "near-implicit");
+    /// assert_eq!("near-implicit".parse(), Ok(AccountType::NearImplicitAccount));
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::NamedAccount => "named",
+            Self::NearImplicitAccount => "near-implicit",
+            Self::EthImplicitAccount => "eth-implicit",
+            Self::NearDeterministicAccount => "near-deterministic",
+        }
+    }
+}
+
+impl core::fmt::Display for AccountType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl core::str::FromStr for AccountType {
+    type Err = ParseAccountTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "named" => Ok(Self::NamedAccount),
+            "near-implicit" => Ok(Self::NearImplicitAccount),
+            "eth-implicit" => Ok(Self::EthImplicitAccount),
+            "near-deterministic" => Ok(Self::NearDeterministicAccount),
+            _ => Err(ParseAccountTypeError(())),
+        }
+    }
 }
 
 impl AccountIdRef {
@@ -61,6 +144,64 @@ impl AccountIdRef {
     pub const MIN_LEN: usize = crate::validation::MIN_LEN;
     /// Longest valid length for a NEAR Account ID.
     pub const MAX_LEN: usize = crate::validation::MAX_LEN;
+    /// Most labels a valid NEAR Account ID can have, i.e. `32`.
+    ///
+    /// Each label takes at least two of the [`MAX_LEN`](Self::MAX_LEN) characters, one for
+    /// itself and one for the following `.`, except for the last one. This also bounds how
+    /// many times [`AccountIdRef::get_parent_account_id`] can be applied, see
+    /// [`AccountIdRef::max_possible_depth`], so arrays of this size can hold all labels or
+    /// ancestors of any Account ID.
+    pub const MAX_LABELS: usize = crate::validation::MAX_LABELS;
+
+    /// Returns the most ancestors a valid Account ID can have, i.e. `31`.
+    ///
+    /// This is how many times [`AccountIdRef::get_parent_account_id`] can return a parent in a
+    /// row, starting from any valid Account ID. Walking up the parents of untrusted input is
+    /// bounded by it, since every parent has one label less than its child.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// assert_eq!(AccountIdRef::max_possible_depth(), AccountIdRef::MAX_LABELS - 1);
+    /// ```
+    pub const fn max_possible_depth() -> usize {
+        Self::MAX_LABELS - 1
+    }
+
+    /// The system account.
+    ///
+    /// See [System account](https://nomicon.io/DataStructures/Account.html?highlight=system#system-account).
+    pub const SYSTEM: &'static AccountIdRef = AccountIdRef::new_or_panic("system");
+
+    /// The NEAR-implicit account made up of 64 zeros.
+    ///
+    /// This is a canonical sentinel, e.g. for a `Default` impl of your own, but note that it's
+    /// a real, valid implicit account, not a null value: it's the implicit account of the
+    /// all-zeros public key, and can receive funds like any other account.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef, AccountType};
+    ///
+    /// struct Transfer {
+    ///     receiver_id: AccountId,
+    /// }
+    ///
+    /// impl Default for Transfer {
+    ///     fn default() -> Self {
+    ///         Self { receiver_id: AccountIdRef::ZERO_IMPLICIT.into() }
+    ///     }
+    /// }
+    ///
+    /// let transfer = Transfer::default();
+    /// assert_eq!(transfer.receiver_id.get_account_type(), AccountType::NearImplicitAccount);
+    /// ```
+    pub const ZERO_IMPLICIT: &'static AccountIdRef = AccountIdRef::new_or_panic(
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    );
 
     /// Construct a [`&AccountIdRef`](AccountIdRef) from a string reference.
     ///
@@ -75,6 +216,51 @@ impl AccountIdRef {
         Ok(unsafe { &*(id as *const str as *const Self) })
     }
 
+    /// Parses a string slice as a [`&AccountIdRef`](AccountIdRef) borrowing from it.
+    ///
+    /// This is the same as [`AccountIdRef::new`], spelled out for the common "check then borrow"
+    /// pattern: the returned reference lives exactly as long as the input, and nothing is allocated.
+    /// Use [`AccountId::parse`] to get an owned Account ID instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let input = String::from("alice.near");
+    /// let alice: &AccountIdRef = AccountIdRef::parse(&input).unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// assert!(AccountIdRef::parse("Alice.near").is_err());
+    /// ```
+    #[inline]
+    pub fn parse(id: &str) -> Result<&Self, ParseAccountError> {
+        Self::new(id)
+    }
+
+    /// Construct a [`&AccountIdRef`](AccountIdRef) from a byte slice.
+    ///
+    /// This constructor validates the provided bytes directly, without a separate UTF-8 validation
+    /// pass, and will produce an error when validation fails.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::from_bytes(b"alice.near").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// assert!(AccountIdRef::from_bytes(b"alice\xff.near").is_err());
+    /// ```
+    pub fn from_bytes(id: &[u8]) -> Result<&Self, ParseAccountError> {
+        crate::validation::validate_bytes(id)?;
+
+        // Safety: a validated Account ID only consists of ASCII characters, which is valid UTF-8.
+        let id = unsafe { core::str::from_utf8_unchecked(id) };
+        Ok(Self::new_unvalidated(id))
+    }
+
     /// Construct a [`&AccountIdRef`](AccountIdRef) from with validation at compile time.
     /// This constructor will panic if validation fails.
     /// ```rust
@@ -87,6 +273,31 @@ impl AccountIdRef {
         unsafe { &*(id as *const str as *const Self) }
     }
 
+    /// Construct a [`&AccountIdRef`](AccountIdRef) with validation at compile time, without panicking.
+    ///
+    /// Unlike [`AccountIdRef::new_or_panic`], this returns the kind of the violation, letting
+    /// the caller (e.g. a macro) decide how to report it.
+    ///
+    /// ```rust
+    /// use near_account_id::{AccountIdRef, ParseErrorKind};
+    ///
+    /// const ALICE: &AccountIdRef = match AccountIdRef::new_const("alice.near") {
+    ///     Ok(account_id) => account_id,
+    ///     Err(_) => panic!("invalid account ID"),
+    /// };
+    /// assert_eq!(ALICE, "alice.near");
+    ///
+    /// const INVALID: Result<&AccountIdRef, ParseErrorKind> = AccountIdRef::new_const("alice..near");
+    /// assert_eq!(INVALID, Err(ParseErrorKind::RedundantSeparator));
+    /// ```
+    pub const fn new_const(id: &str) -> Result<&Self, ParseErrorKind> {
+        match crate::validation::validate_const_result(id) {
+            // Safety: see `AccountIdRef::new`
+            Ok(()) => Ok(unsafe { &*(id as *const str as *const Self) }),
+            Err(kind) => Err(kind),
+        }
+    }
+
     /// Construct a [`&AccountIdRef`](AccountIdRef) from a string reference without validating the address.
     /// It is the responsibility of the caller to ensure the account ID is valid.
     ///
@@ -97,8 +308,11 @@ impl AccountIdRef {
         // In order to avoid protocol change, `internal_unstable` feature was implemented and it is
         // expected that AccountId might be invalid and it will be explicitly validated at the
         // later stage.
+        //
+        // The length is not checked here, since `AccountId::parse_with_limits` may construct
+        // Account IDs longer than `MAX_LEN`.
         #[cfg(not(feature = "internal_unstable"))]
-        debug_assert!(crate::validation::validate(id).is_ok());
+        debug_assert!(crate::validation::validate_with_limits(id, usize::MAX).is_ok());
 
         // Safety: see `AccountIdRef::new`
         unsafe { &*(id as *const str as *const Self) }
@@ -143,6 +357,32 @@ impl AccountIdRef {
         !self.is_system() && !self.0.contains('.')
     }
 
+    /// Returns `true` if the account ID is a top-level account that only the registrar can create.
+    ///
+    /// Per the protocol, top-level accounts from 32 up to [`AccountIdRef::MAX_LEN`] characters
+    /// long, like implicit accounts, can be created by anyone. Shorter ones, like `near` or
+    /// `testnet`, are reserved for the registrar, and so are longer ones (see
+    /// [`AccountId::parse_with_limits`]), as per
+    /// [NEP-492](https://github.com/near/NEPs/pull/492). Sub-accounts can
+    /// only be created by their parent account, so this returns `false` for them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// assert!(AccountIdRef::new_or_panic("near").is_registrar_controlled_tla());
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_registrar_controlled_tla());
+    ///
+    /// let implicit = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert!(!implicit.is_registrar_controlled_tla());
+    /// ```
+    pub fn is_registrar_controlled_tla(&self) -> bool {
+        self.is_top_level()
+            && (self.len() < crate::validation::MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LEN
+                || self.len() > Self::MAX_LEN)
+    }
+
     /// Returns `true` if the `AccountId` is a direct sub-account of the provided parent account.
     ///
     /// See [Subaccounts](https://docs.near.org/docs/concepts/account#subaccounts).
@@ -172,603 +412,2930 @@ impl AccountIdRef {
             .map_or(false, |s| !s.contains('.'))
     }
 
-    /// Returns `AccountType::EthImplicitAccount` if the `AccountId` is a 40 characters long hexadecimal prefixed with '0x'.
-    /// Returns `AccountType::NearImplicitAccount` if the `AccountId` is a 64 characters long hexadecimal.
-    /// Otherwise, returns `AccountType::NamedAccount`.
+    /// Returns `true` if `suffix` is made up of the trailing labels of this account ID,
+    /// or is this account ID itself.
     ///
-    /// See [Implicit-Accounts](https://docs.near.org/docs/concepts/account#implicit-accounts).
+    /// Unlike [`AccountIdRef::is_sub_account_of`], this matches sub-accounts at any depth,
+    /// and the account ID itself.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use near_account_id::{AccountId, AccountType};
-    ///
-    /// let alice: AccountId = "alice.near".parse().unwrap();
-    /// assert!(alice.get_account_type() == AccountType::NamedAccount);
+    /// use near_account_id::AccountIdRef;
     ///
-    /// let eth_rando = "0xb794f5ea0ba39494ce839613fffba74279579268"
-    ///     .parse::<AccountId>()
-    ///     .unwrap();
-    /// assert!(eth_rando.get_account_type() == AccountType::EthImplicitAccount);
+    /// let validator = AccountIdRef::new_or_panic("x.pool.near");
     ///
-    /// let near_rando = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
-    ///     .parse::<AccountId>()
-    ///     .unwrap();
-    /// assert!(near_rando.get_account_type() == AccountType::NearImplicitAccount);
+    /// assert!(validator.ends_with_labels(AccountIdRef::new_or_panic("pool.near")));
+    /// assert!(validator.ends_with_labels(AccountIdRef::new_or_panic("near")));
+    /// assert!(validator.ends_with_labels(validator));
+    /// assert!(!validator.ends_with_labels(AccountIdRef::new_or_panic("ool.near")));
     /// ```
-    pub fn get_account_type(&self) -> AccountType {
-        if crate::validation::is_eth_implicit(self.as_str()) {
-            return AccountType::EthImplicitAccount;
+    pub fn ends_with_labels(&self, suffix: &AccountIdRef) -> bool {
+        match self.0.strip_suffix(suffix.as_str()) {
+            Some(rest) => rest.is_empty() || rest.ends_with('.'),
+            None => false,
         }
-        if crate::validation::is_near_implicit(self.as_str()) {
-            return AccountType::NearImplicitAccount;
+    }
+
+    /// Returns `true` if `prefix` is made up of the leading labels of this account ID,
+    /// or is this account ID itself.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// assert!(app.starts_with_labels(AccountIdRef::new_or_panic("app.alice")));
+    /// assert!(app.starts_with_labels(app));
+    /// assert!(!app.starts_with_labels(AccountIdRef::new_or_panic("ap")));
+    /// ```
+    pub fn starts_with_labels(&self, prefix: &AccountIdRef) -> bool {
+        match self.0.strip_prefix(prefix.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with('.'),
+            None => false,
         }
-        AccountType::NamedAccount
     }
 
-    /// Returns `true` if this `AccountId` is the system account.
+    /// Returns the labels of this account ID that precede `ancestor`, or `None` if this account ID
+    /// is not a sub-account of `ancestor` at any depth.
     ///
-    /// See [System account](https://nomicon.io/DataStructures/Account.html?highlight=system#system-account).
+    /// This is the textual complement of [`AccountIdRef::ends_with_labels`], except that an
+    /// account ID is not its own ancestor.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use near_account_id::AccountId;
+    /// use near_account_id::AccountIdRef;
     ///
-    /// let alice: AccountId = "alice.near".parse().unwrap();
-    /// assert!(!alice.is_system());
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
     ///
-    /// let system: AccountId = "system".parse().unwrap();
-    /// assert!(system.is_system());
+    /// assert_eq!(app.strip_suffix_account(AccountIdRef::new_or_panic("near")), Some("app.alice"));
+    /// assert_eq!(app.strip_suffix_account(AccountIdRef::new_or_panic("alice.near")), Some("app"));
+    /// assert_eq!(app.strip_suffix_account(app), None);
+    /// assert_eq!(app.strip_suffix_account(AccountIdRef::new_or_panic("ce.near")), None);
     /// ```
-    pub fn is_system(&self) -> bool {
-        self == "system"
+    pub fn strip_suffix_account(&self, ancestor: &AccountIdRef) -> Option<&str> {
+        self.0.strip_suffix(ancestor.as_str())?.strip_suffix('.')
     }
 
-    /// Returns the length of the underlying account id string.
-    pub const fn len(&self) -> usize {
-        self.0.len()
+    /// Returns how many levels this account ID is below `ancestor`, or `None` if this account ID
+    /// is not a sub-account of `ancestor` at any depth.
+    ///
+    /// This is the number of labels [`AccountIdRef::strip_suffix_account`] strips, so a direct
+    /// sub-account is `1` level below its parent.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// assert_eq!(app.levels_below(AccountIdRef::new_or_panic("near")), Some(2));
+    /// assert_eq!(app.levels_below(AccountIdRef::new_or_panic("alice.near")), Some(1));
+    /// assert_eq!(app.levels_below(app), None);
+    /// assert_eq!(app.levels_below(AccountIdRef::new_or_panic("bob.near")), None);
+    /// ```
+    pub fn levels_below(&self, ancestor: &AccountIdRef) -> Option<usize> {
+        let labels = self.strip_suffix_account(ancestor)?;
+        Some(labels.matches('.').count() + 1)
     }
 
-    /// Returns parent's account id reference
+    /// Returns `true` if the account ID matches `pattern`, e.g. for allow-lists.
+    ///
+    /// The pattern is matched label by label, from left to right:
+    ///
+    /// * A label that is exactly `**` matches one or more whole labels.
+    /// * Otherwise, `*` matches any run of characters within a single label, so it never
+    ///   crosses a `.`. A label that is exactly `*` matches any one label.
+    /// * Everything else has to match literally.
+    ///
+    /// Wildcards never match zero labels, so neither `*.near` nor `**.near` match `near`.
     ///
     /// ## Examples
+    ///
     /// ```
     /// use near_account_id::AccountIdRef;
     ///
-    /// let alice: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
-    /// let parent: &AccountIdRef = alice.get_parent_account_id().unwrap();
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
     ///
-    /// assert!(alice.is_sub_account_of(parent));
+    /// assert!(app.matches_pattern("app.*.near"));
+    /// assert!(app.matches_pattern("**.near"));
+    /// assert!(app.matches_pattern("app.**"));
+    /// assert!(app.matches_pattern("app.ali*.near"));
+    /// assert!(!app.matches_pattern("*.near"));
+    /// assert!(!app.matches_pattern("app.*"));
+    /// ```
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        matches_labels(pattern, self.as_str())
+    }
+
+    /// Returns `true` if the account ID is a direct sub-account of some top-level account,
+    /// i.e. it consists of exactly two labels, like `alice.near`.
     ///
-    /// let near: &AccountIdRef = AccountIdRef::new_or_panic("near");
+    /// Implicit accounts never contain a `.`, so they are never second-level accounts.
     ///
-    /// assert!(near.get_parent_account_id().is_none());
+    /// ## Examples
     ///
-    /// let implicit: &AccountIdRef = AccountIdRef::new_or_panic("248e104d1d4764d713c4211c13808c8fc887869c580f4178e60538ac5c2a0b26");
+    /// ```
+    /// use near_account_id::AccountIdRef;
     ///
-    /// assert!(implicit.get_parent_account_id().is_none());
+    /// assert!(AccountIdRef::new_or_panic("alice.near").is_second_level());
+    ///
+    /// assert!(!AccountIdRef::new_or_panic("near").is_second_level());
+    /// assert!(!AccountIdRef::new_or_panic("app.alice.near").is_second_level());
     /// ```
-    pub fn get_parent_account_id(&self) -> Option<&AccountIdRef> {
-        let parent_str = self.as_str().split_once('.')?.1;
-        Some(AccountIdRef::new_unvalidated(parent_str))
+    pub fn is_second_level(&self) -> bool {
+        matches!(self.0.split_once('.'), Some((_, parent)) if !parent.contains('.'))
     }
-}
 
-impl std::fmt::Display for AccountIdRef {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.0, f)
+    /// Returns an iterator over the labels and separators of the account ID, in order.
+    ///
+    /// Unlike the dot-separated labels, every separator (`-`, `_` and `.`) splits the account ID here,
+    /// which gives full fidelity for custom rendering.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountIdRef, Token};
+    ///
+    /// let account_id = AccountIdRef::new_or_panic("alice-b.near");
+    ///
+    /// assert_eq!(
+    ///     account_id.tokens().collect::<Vec<_>>(),
+    ///     [
+    ///         Token::Label("alice"),
+    ///         Token::Separator('-'),
+    ///         Token::Label("b"),
+    ///         Token::Separator('.'),
+    ///         Token::Label("near"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn tokens(&self) -> impl Iterator<Item = Token<'_>> {
+        let mut rest = self.as_str();
+        core::iter::from_fn(move || {
+            let first = *rest.as_bytes().first()?;
+            if crate::validation::is_separator(first) {
+                rest = &rest[1..];
+                return Some(Token::Separator(first as char));
+            }
+            let end = rest
+                .bytes()
+                .position(crate::validation::is_separator)
+                .unwrap_or(rest.len());
+            let (label, tail) = rest.split_at(end);
+            rest = tail;
+            Some(Token::Label(label))
+        })
     }
-}
-
-impl ToOwned for AccountIdRef {
-    type Owned = AccountId;
 
-    fn to_owned(&self) -> Self::Owned {
-        AccountId(self.0.into())
+    /// Returns an iterator over the labels of the account ID, i.e. its dot-separated parts.
+    ///
+    /// See [`AccountId::from_labels`] for the inverse.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice_app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// assert!(alice_app.labels().eq(["app", "alice", "near"]));
+    /// assert_eq!(alice_app.labels().next_back(), Some("near"));
+    /// ```
+    pub fn labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.0.split('.')
     }
-}
 
-impl<'a> From<&'a AccountIdRef> for AccountId {
-    fn from(id: &'a AccountIdRef) -> Self {
-        id.to_owned()
+    /// Collects the labels of the account ID into an array, without allocating.
+    ///
+    /// Returns the array and the number of labels, which fill the start of the array. The rest
+    /// is left empty. An array of [`AccountIdRef::MAX_LABELS`] always fits all labels, smaller
+    /// ones return [`TooManyLabels`] if the account ID has more than `N` labels.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice_app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// let (labels, len) = alice_app.labels_into::<4>().unwrap();
+    /// assert_eq!(labels[..len], ["app", "alice", "near"]);
+    ///
+    /// assert!(alice_app.labels_into::<2>().is_err());
+    /// ```
+    pub fn labels_into<const N: usize>(&self) -> Result<([&str; N], usize), TooManyLabels> {
+        let mut labels = [""; N];
+        let mut len = 0;
+        for label in self.labels() {
+            *labels.get_mut(len).ok_or(TooManyLabels(()))? = label;
+            len += 1;
+        }
+        Ok((labels, len))
     }
-}
 
-impl<'s> TryFrom<&'s str> for &'s AccountIdRef {
-    type Error = ParseAccountError;
-
-    fn try_from(value: &'s str) -> Result<Self, Self::Error> {
-        AccountIdRef::new(value)
+    /// Splits the account ID into its first label and the account ID made up of the rest.
+    ///
+    /// The rest is the same as [`AccountIdRef::get_parent_account_id`], and is valid by
+    /// construction, as long as it's long enough: it's `None` for single-label account IDs,
+    /// and for a single-character rest like the `a` of `app.a`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let (first, rest) = AccountIdRef::new_or_panic("app.alice.near").split_first_label();
+    /// assert_eq!(first, "app");
+    /// assert_eq!(rest.unwrap(), "alice.near");
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near").split_first_label(), ("near", None));
+    /// ```
+    pub fn split_first_label(&self) -> (&str, Option<&AccountIdRef>) {
+        match self.0.split_once('.') {
+            Some((first, rest)) => (first, Self::new_if_long_enough(rest)),
+            None => (&self.0, None),
+        }
     }
-}
 
-impl AsRef<str> for AccountIdRef {
-    fn as_ref(&self) -> &str {
-        &self.0
+    /// Splits the account ID into the account ID made up of all but its last label, and its last
+    /// label, i.e. its top-level account.
+    ///
+    /// Like with [`AccountIdRef::split_first_label`], the leading labels are valid by
+    /// construction, as long as they're long enough: they're `None` for single-label account IDs,
+    /// and for a single-character prefix like the `a` of `a.near`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let (rest, last) = AccountIdRef::new_or_panic("app.alice.near").split_last_label();
+    /// assert_eq!(rest.unwrap(), "app.alice");
+    /// assert_eq!(last, "near");
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near").split_last_label(), (None, "near"));
+    /// ```
+    pub fn split_last_label(&self) -> (Option<&AccountIdRef>, &str) {
+        match self.0.rsplit_once('.') {
+            Some((rest, last)) => (Self::new_if_long_enough(rest), last),
+            None => (None, &self.0),
+        }
     }
-}
 
-impl PartialEq<AccountIdRef> for String {
-    fn eq(&self, other: &AccountIdRef) -> bool {
-        self == &other.0
+    /// Wraps labels split off a valid account ID, which are only invalid if they're too short.
+    fn new_if_long_enough(labels: &str) -> Option<&AccountIdRef> {
+        (labels.len() >= Self::MIN_LEN).then(|| Self::new_unvalidated(labels))
     }
-}
 
-impl PartialEq<String> for AccountIdRef {
-    fn eq(&self, other: &String) -> bool {
-        &self.0 == other
+    /// Returns `true` if the number of labels in the account ID falls within the provided range.
+    ///
+    /// Labels are the dot-separated parts of the account ID, e.g. `app.alice.near` has 3 labels.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice_app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// assert!(alice_app.depth_in_range(2..=4));
+    /// assert!(!alice_app.depth_in_range(1..=2));
+    /// ```
+    pub fn depth_in_range(&self, range: RangeInclusive<usize>) -> bool {
+        range.contains(&(self.0.matches('.').count() + 1))
     }
-}
 
-impl PartialEq<AccountIdRef> for str {
+    /// Returns `AccountType::EthImplicitAccount` if the `AccountId` is a 40 characters long hexadecimal prefixed with '0x'.
+    /// Returns `AccountType::NearImplicitAccount` if the `AccountId` is a 64 characters long hexadecimal.
+    /// Returns `AccountType::NearDeterministicAccount` if the `AccountId` is a 40 characters long hexadecimal prefixed with '0s'.
+    /// Otherwise, returns `AccountType::NamedAccount`.
+    ///
+    /// See [Implicit-Accounts](https://docs.near.org/docs/concepts/account#implicit-accounts).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountType};
+    ///
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// assert!(alice.get_account_type() == AccountType::NamedAccount);
+    ///
+    /// let eth_rando = "0xb794f5ea0ba39494ce839613fffba74279579268"
+    ///     .parse::<AccountId>()
+    ///     .unwrap();
+    /// assert!(eth_rando.get_account_type() == AccountType::EthImplicitAccount);
+    ///
+    /// let near_rando = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
+    ///     .parse::<AccountId>()
+    ///     .unwrap();
+    /// assert!(near_rando.get_account_type() == AccountType::NearImplicitAccount);
+    ///
+    /// let deterministic_rando = "0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b"
+    ///     .parse::<AccountId>()
+    ///     .unwrap();
+    /// assert!(deterministic_rando.get_account_type() == AccountType::NearDeterministicAccount);
+    /// ```
+    pub fn get_account_type(&self) -> AccountType {
+        if crate::validation::is_eth_implicit(self.as_str()) {
+            return AccountType::EthImplicitAccount;
+        }
+        if crate::validation::is_near_implicit(self.as_str()) {
+            return AccountType::NearImplicitAccount;
+        }
+        if crate::validation::is_near_deterministic(self.as_str()) {
+            return AccountType::NearDeterministicAccount;
+        }
+        AccountType::NamedAccount
+    }
+
+    /// Returns `true` if this is an implicit account, i.e. either NEAR-implicit, ETH-implicit or NEAR-deterministic.
+    ///
+    /// See [`AccountType::is_implicit`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// assert!(AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268").is_implicit());
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_implicit());
+    /// ```
+    pub fn is_implicit(&self) -> bool {
+        self.get_account_type().is_implicit()
+    }
+
+    /// Returns `true` if this is a named account, i.e. neither NEAR-implicit, ETH-implicit nor NEAR-deterministic.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// assert!(AccountIdRef::new_or_panic("alice.near").is_named());
+    /// assert!(!AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268").is_named());
+    /// ```
+    pub fn is_named(&self) -> bool {
+        self.get_account_type() == AccountType::NamedAccount
+    }
+
+    /// Returns `true` if this is a NEAR-implicit account, i.e. a 64 characters long hexadecimal.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let near_rando = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+    /// assert!(AccountIdRef::new_or_panic(near_rando).is_near_implicit());
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_near_implicit());
+    /// ```
+    pub fn is_near_implicit(&self) -> bool {
+        crate::validation::is_near_implicit(self.as_str())
+    }
+
+    /// Same as [`AccountIdRef::is_near_implicit`], but usable in const contexts, e.g. to assert
+    /// at compile time that an Account ID constant is NEAR-implicit.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// const TREASURY: &AccountIdRef = AccountIdRef::new_or_panic(
+    ///     "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+    /// );
+    /// const _: () = assert!(TREASURY.is_near_implicit_const());
+    ///
+    /// const ALICE: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
+    /// const _: () = assert!(!ALICE.is_near_implicit_const());
+    /// ```
+    pub const fn is_near_implicit_const(&self) -> bool {
+        crate::validation::is_near_implicit_const(&self.0)
+    }
+
+    /// Returns `true` if this is an ETH-implicit account, i.e. a 40 characters long hexadecimal prefixed with '0x'.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let eth_rando = "0xb794f5ea0ba39494ce839613fffba74279579268";
+    /// assert!(AccountIdRef::new_or_panic(eth_rando).is_eth_implicit());
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_eth_implicit());
+    /// ```
+    pub fn is_eth_implicit(&self) -> bool {
+        crate::validation::is_eth_implicit(self.as_str())
+    }
+
+    /// Returns `true` if this is a NEAR-deterministic account, i.e. a 40 characters long hexadecimal prefixed with '0s'.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let deterministic_rando = "0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b";
+    /// assert!(AccountIdRef::new_or_panic(deterministic_rando).is_near_deterministic());
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_near_deterministic());
+    /// ```
+    pub fn is_near_deterministic(&self) -> bool {
+        crate::validation::is_near_deterministic(self.as_str())
+    }
+
+    /// Returns the ed25519 public key of a NEAR-implicit account.
+    ///
+    /// Returns `None` if this is not a [`AccountType::NearImplicitAccount`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let implicit = AccountId::from_near_implicit(&[0xab; 32]);
+    /// assert_eq!(implicit.near_implicit_public_key(), Some([0xab; 32]));
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.near_implicit_public_key(), None);
+    /// ```
+    pub fn near_implicit_public_key(&self) -> Option<[u8; 32]> {
+        if self.get_account_type() != AccountType::NearImplicitAccount {
+            return None;
+        }
+        crate::hex::decode(self.as_bytes())
+    }
+
+    /// Returns the 64 characters long hexadecimal of a NEAR-implicit account, without copying.
+    ///
+    /// Returns `None` if this is not a [`AccountType::NearImplicitAccount`].
+    /// See [`AccountIdRef::near_implicit_public_key`] to get the decoded bytes instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let near_rando = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+    /// assert_eq!(AccountIdRef::new_or_panic(near_rando).near_implicit_hex(), Some(near_rando));
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("alice.near").near_implicit_hex(), None);
+    /// ```
+    pub fn near_implicit_hex(&self) -> Option<&str> {
+        self.is_near_implicit().then(|| self.as_str())
+    }
+
+    /// Returns the Ethereum address of an ETH-implicit account.
+    ///
+    /// Returns `None` if this is not a [`AccountType::EthImplicitAccount`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let implicit = AccountId::from_eth_implicit(&[0xab; 20]);
+    /// assert_eq!(implicit.eth_address(), Some([0xab; 20]));
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.eth_address(), None);
+    /// ```
+    pub fn eth_address(&self) -> Option<[u8; 20]> {
+        if self.get_account_type() != AccountType::EthImplicitAccount {
+            return None;
+        }
+        crate::hex::decode(&self.as_bytes()[2..])
+    }
+
+    /// Returns `true` if this is an ETH-implicit account of the Ethereum address `addr`.
+    ///
+    /// This compares the decoded address, so callers don't need to format `addr` first.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let eth = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// let mut addr = [
+    ///     0xb7, 0x94, 0xf5, 0xea, 0x0b, 0xa3, 0x94, 0x94, 0xce, 0x83,
+    ///     0x96, 0x13, 0xff, 0xfb, 0xa7, 0x42, 0x79, 0x57, 0x92, 0x68,
+    /// ];
+    /// assert!(eth.eq_eth_address(&addr));
+    ///
+    /// addr[19] = 0x69;
+    /// assert!(!eth.eq_eth_address(&addr));
+    /// ```
+    pub fn eq_eth_address(&self, addr: &[u8; 20]) -> bool {
+        self.eth_address().as_ref() == Some(addr)
+    }
+
+    /// Returns `true` if `other` is equal to this account ID, ignoring ASCII case.
+    ///
+    /// This is meant for migrating records stored before validation rejected uppercase
+    /// letters, such as `Alice.near`. Parsing stays case-sensitive: `other` doesn't have to be
+    /// a valid account ID. See [`AccountIdRef::eq_hex_ignore_case`] to only tolerate case
+    /// differences in implicit accounts.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// assert!(alice.eq_ignore_ascii_case("Alice.NEAR"));
+    /// assert!(!alice.eq_ignore_ascii_case("bob.near"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+
+    /// Returns `true` if this is an implicit account equal to `other`, ignoring ASCII case.
+    ///
+    /// Wallets sometimes uppercase the hex of implicit accounts, which this tolerates without
+    /// allocating. Named accounts are case-sensitive, so this always returns `false` for them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let eth = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert!(eth.eq_hex_ignore_case("0xB794F5eA0ba39494cE839613fffBA74279579268"));
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert!(!alice.eq_hex_ignore_case("Alice.near"));
+    /// assert!(!alice.eq_hex_ignore_case("alice.near"));
+    /// ```
+    pub fn eq_hex_ignore_case(&self, other: &str) -> bool {
+        self.is_implicit() && self.0.eq_ignore_ascii_case(other)
+    }
+
+    /// Returns the canonical form of the account ID, to be used as a lookup key.
+    ///
+    /// ETH-implicit accounts are lowercased, so that an address in its checksummed form maps
+    /// to the same key as its lowercase form. Named and NEAR-implicit accounts are returned as is.
+    ///
+    /// Validated account IDs are always lowercase, so this only allocates for account IDs
+    /// constructed without validation.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    /// use std::borrow::Cow;
+    ///
+    /// let eth = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert!(matches!(eth.canonical_key(), Cow::Borrowed("0xb794f5ea0ba39494ce839613fffba74279579268")));
+    /// ```
+    pub fn canonical_key(&self) -> Cow<'_, str> {
+        let is_eth_implicit_ignoring_case = self.len() == 42
+            && self.0.starts_with("0x")
+            && self.as_bytes()[2..].iter().all(u8::is_ascii_hexdigit);
+
+        if is_eth_implicit_ignoring_case && self.as_bytes().iter().any(u8::is_ascii_uppercase) {
+            Cow::Owned(self.0.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(self.as_str())
+        }
+    }
+
+    /// Returns `true` if this `AccountId` is the system account.
+    ///
+    /// See [System account](https://nomicon.io/DataStructures/Account.html?highlight=system#system-account).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountId;
+    ///
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// assert!(!alice.is_system());
+    ///
+    /// let system: AccountId = "system".parse().unwrap();
+    /// assert!(system.is_system());
+    /// ```
+    pub fn is_system(&self) -> bool {
+        self == Self::SYSTEM
+    }
+
+    /// Returns the reason why this account ID cannot be registered, if any.
+    ///
+    /// This checks registration eligibility, as opposed to the grammar checked when parsing:
+    /// reserved account IDs like `system` and implicit accounts are valid account IDs,
+    /// but cannot be registered.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountIdRef, RegistrationError};
+    ///
+    /// let system = AccountIdRef::new_or_panic("system");
+    /// assert_eq!(system.registration_error(), Some(RegistrationError::Reserved));
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.registration_error(), None);
+    /// ```
+    pub fn registration_error(&self) -> Option<RegistrationError> {
+        if crate::validation::is_reserved(self.as_str()) {
+            Some(RegistrationError::Reserved)
+        } else if self.is_implicit() {
+            Some(RegistrationError::Implicit)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position and the character of the first validation failure,
+    /// or `None` if there is none.
+    ///
+    /// This is a lightweight audit helper for account IDs that skipped validation: a single
+    /// pass that stops at the first violation, without allocating. Length violations are not
+    /// tied to any character, so they are not reported here, use [`AccountId::validate`] for a
+    /// full check.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.first_invalid(), None);
+    /// ```
+    pub fn first_invalid(&self) -> Option<(usize, char)> {
+        crate::validation::validate_format(self.as_bytes())
+            .err()?
+            .char
+    }
+
+    /// Returns a stable 128-bit fingerprint of the account ID, e.g. for placement on a
+    /// consistent-hashing ring.
+    ///
+    /// The fingerprint is the [XXH3](https://github.com/Cyan4973/xxHash/blob/dev/doc/xxhash_spec.md)
+    /// 128-bit hash (with the default seed `0`) of the account ID bytes. This algorithm is part of
+    /// the API and will not change across crate versions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// let bob = AccountIdRef::new_or_panic("bob.near");
+    ///
+    /// assert_eq!(alice.fingerprint128(), alice.to_owned().fingerprint128());
+    /// assert_ne!(alice.fingerprint128(), bob.fingerprint128());
+    /// ```
+    #[cfg(feature = "fingerprint")]
+    pub fn fingerprint128(&self) -> u128 {
+        xxhash_rust::xxh3::xxh3_128(self.as_bytes())
+    }
+
+    /// Returns the hash of the account ID that the protocol uses, e.g. for shard assignment.
+    ///
+    /// This is the SHA-256 digest of the account ID bytes, same as nearcore's
+    /// `CryptoHash::hash_bytes(account_id.as_bytes())`. This algorithm is part of the API and
+    /// will not change across crate versions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// assert_eq!(alice.hash_bytes()[..4], [0x2d, 0xd5, 0xdd, 0xa5]);
+    /// assert_eq!(alice.hash_bytes(), alice.to_owned().hash_bytes());
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn hash_bytes(&self) -> [u8; 32] {
+        use sha2::Digest;
+
+        sha2::Sha256::digest(self.as_bytes()).into()
+    }
+
+    /// Returns the shard of the account ID in a hash-based shard layout with `num_shards` shards.
+    ///
+    /// This is nearcore's assignment for version 0 shard layouts: the first 8 bytes of
+    /// [`AccountIdRef::hash_bytes`], read as a little-endian `u64`, modulo `num_shards`.
+    /// Later shard layouts assign shards by boundary accounts instead, which depend on the
+    /// layout and aren't covered here.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `num_shards` is `0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// assert_eq!(alice.shard_id(1), 0);
+    /// assert_eq!(alice.shard_id(8), 5);
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn shard_id(&self, num_shards: u64) -> u64 {
+        let hash = self.hash_bytes();
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        u64::from_le_bytes(bytes) % num_shards
+    }
+
+    /// Returns the [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksummed address of an
+    /// ETH-implicit account, or `None` for any other type of account.
+    ///
+    /// This is for display only: the mixed-case result is no longer a valid account ID.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let eth = AccountIdRef::new_or_panic("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+    /// assert_eq!(eth.to_eip55().unwrap(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("alice.near").to_eip55(), None);
+    /// ```
+    #[cfg(feature = "eip55")]
+    pub fn to_eip55(&self) -> Option<String> {
+        use sha3::Digest;
+
+        if self.get_account_type() != AccountType::EthImplicitAccount {
+            return None;
+        }
+        let address = &self.0[2..];
+        let hash = sha3::Keccak256::digest(address.as_bytes());
+
+        let mut checksummed = String::with_capacity(42);
+        checksummed.push_str("0x");
+        for (idx, c) in address.chars().enumerate() {
+            // Uppercase letters whose corresponding nibble of the hash is at least 8.
+            let nibble = if idx % 2 == 0 {
+                hash[idx / 2] >> 4
+            } else {
+                hash[idx / 2] & 0xf
+            };
+            checksummed.push(if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            });
+        }
+        Some(checksummed)
+    }
+
+    /// Returns a [`Display`](core::fmt::Display)able form of the account ID that abbreviates
+    /// implicit accounts, e.g. for logs.
+    ///
+    /// Implicit accounts are shown as their first and last 4 characters around an ellipsis,
+    /// while named accounts are shown verbatim. Formatting doesn't allocate.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let implicit = AccountIdRef::new_or_panic(
+    ///     "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+    /// );
+    /// assert_eq!(implicit.truncated().to_string(), "9879…d6de");
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.truncated().to_string(), "alice.near");
+    /// ```
+    pub fn truncated(&self) -> impl core::fmt::Display + '_ {
+        struct Truncated<'a>(&'a AccountIdRef);
+
+        impl core::fmt::Display for Truncated<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let account_id = self.0.as_str();
+                if self.0.is_implicit() {
+                    write!(
+                        f,
+                        "{}…{}",
+                        &account_id[..4],
+                        &account_id[account_id.len() - 4..]
+                    )
+                } else {
+                    f.write_str(account_id)
+                }
+            }
+        }
+
+        Truncated(self)
+    }
+
+    /// Returns the length of the underlying account id string.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns parent's account id reference
+    ///
+    /// ## Examples
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
+    /// let parent: &AccountIdRef = alice.get_parent_account_id().unwrap();
+    ///
+    /// assert!(alice.is_sub_account_of(parent));
+    ///
+    /// let near: &AccountIdRef = AccountIdRef::new_or_panic("near");
+    ///
+    /// assert!(near.get_parent_account_id().is_none());
+    ///
+    /// let implicit: &AccountIdRef = AccountIdRef::new_or_panic("248e104d1d4764d713c4211c13808c8fc887869c580f4178e60538ac5c2a0b26");
+    ///
+    /// assert!(implicit.get_parent_account_id().is_none());
+    /// ```
+    pub fn get_parent_account_id(&self) -> Option<&AccountIdRef> {
+        let parent_str = self.as_str().split_once('.')?.1;
+        Some(AccountIdRef::new_unvalidated(parent_str))
+    }
+
+    /// Returns how many more characters fit in this account ID under [`AccountId::MAX_LEN`].
+    ///
+    /// This is `0` for account IDs that are already [`AccountId::MAX_LEN`] long, or longer
+    /// (see [`AccountId::parse_with_limits`]).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.remaining_capacity(), AccountId::MAX_LEN - 10);
+    /// ```
+    pub const fn remaining_capacity(&self) -> usize {
+        AccountId::MAX_LEN.saturating_sub(self.len())
+    }
+
+    /// Returns `true` if prepending `label` as a sub-account stays within [`AccountId::MAX_LEN`].
+    ///
+    /// This only checks the length, which is the check [`AccountIdRef::make_sub_account`]
+    /// does first. The label itself may still be invalid.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// assert!(alice.can_prepend_label("app"));
+    /// assert!(!alice.can_prepend_label(&"a".repeat(alice.remaining_capacity())));
+    /// assert!(alice.can_prepend_label(&"a".repeat(alice.remaining_capacity() - 1)));
+    /// ```
+    pub fn can_prepend_label(&self, label: &str) -> bool {
+        // The label takes up a separator on top of its own length.
+        label.len() < self.remaining_capacity()
+    }
+
+    /// Creates a sub-account of this account ID by prepending `label`.
+    ///
+    /// This is the inverse of [`AccountIdRef::get_parent_account_id`]. The label must be a single
+    /// valid label, so it must not contain a `.`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`ParseErrorKind::TooLong`] if the sub-account would be longer than
+    /// [`AccountId::MAX_LEN`], otherwise the error of [`AccountId::validate_label`] if the
+    /// label is invalid, with the position in the label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountIdRef, ParseErrorKind};
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// let app = alice.make_sub_account("app").unwrap();
+    /// assert_eq!(app, "app.alice.near");
+    /// assert_eq!(app.get_parent_account_id().unwrap(), alice);
+    ///
+    /// let err = alice.make_sub_account("app.v2").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn make_sub_account(&self, label: &str) -> Result<AccountId, ParseAccountError> {
+        if !self.can_prepend_label(label) {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::TooLong,
+                char: None,
+            });
+        }
+        crate::validation::validate_label(label)?;
+
+        let mut account_id = String::with_capacity(label.len() + 1 + self.len());
+        account_id.push_str(label);
+        account_id.push('.');
+        account_id.push_str(self.as_str());
+        Ok(AccountId(account_id.into_boxed_str()))
+    }
+
+    /// Returns the deepest account that both account IDs are equal to or sub-accounts of.
+    ///
+    /// The account IDs are compared label by label from the right, so partially matching labels
+    /// don't count: `app.near` and `capp.near` only share `near`. Returns `None` if even the
+    /// top-level accounts differ, or if the shared labels are too short to be an account ID.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let a = AccountIdRef::new_or_panic("a.app.near");
+    /// let b = AccountIdRef::new_or_panic("b.app.near");
+    /// assert_eq!(a.common_ancestor(b).unwrap(), "app.near");
+    ///
+    /// let capp = AccountIdRef::new_or_panic("capp.near");
+    /// assert_eq!(a.common_ancestor(capp).unwrap(), "near");
+    ///
+    /// let testnet = AccountIdRef::new_or_panic("a.app.testnet");
+    /// assert!(a.common_ancestor(testnet).is_none());
+    /// ```
+    pub fn common_ancestor<'a>(&'a self, other: &'a AccountIdRef) -> Option<&'a AccountIdRef> {
+        let common_len = self
+            .as_bytes()
+            .iter()
+            .rev()
+            .zip(other.as_bytes().iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let starts_at_label = |account_id: &AccountIdRef| {
+            account_id.len() == common_len
+                || account_id.as_bytes()[account_id.len() - common_len - 1] == b'.'
+        };
+
+        let mut ancestor = &self.0[self.len() - common_len..];
+        if !(starts_at_label(self) && starts_at_label(other)) {
+            ancestor = ancestor.split_once('.')?.1;
+        }
+        (ancestor.len() >= Self::MIN_LEN).then(|| AccountIdRef::new_unvalidated(ancestor))
+    }
+
+    /// Returns the account ID prefixed with the provided string, e.g. for namespaced storage keys.
+    ///
+    /// The resulting string is built with a single allocation of the exact size.
+    ///
+    /// ## Examples
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// assert_eq!(alice.with_prefix("acct:"), "acct:alice.near");
+    /// ```
+    pub fn with_prefix(&self, prefix: &str) -> String {
+        let mut key = String::with_capacity(prefix.len() + self.len());
+        key.push_str(prefix);
+        key.push_str(self.as_str());
+        key
+    }
+
+    /// Returns the account ID escaped as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901)
+    /// path segment.
+    ///
+    /// Applies the RFC 6901 escaping (`~` → `~0`, `/` → `~1`). Neither character is allowed in
+    /// a valid account ID, so in practice the segment is the account ID itself, dots included.
+    ///
+    /// ## Examples
+    /// ```
+    /// use near_account_id::AccountIdRef;
+    ///
+    /// let alice: &AccountIdRef = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// assert_eq!(alice.to_json_pointer_segment(), "app.alice.near");
+    /// ```
+    pub fn to_json_pointer_segment(&self) -> String {
+        let mut segment = String::with_capacity(self.len());
+        for c in self.0.chars() {
+            match c {
+                '~' => segment.push_str("~0"),
+                '/' => segment.push_str("~1"),
+                c => segment.push(c),
+            }
+        }
+        segment
+    }
+}
+
+impl core::fmt::Display for AccountIdRef {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl ToOwned for AccountIdRef {
+    type Owned = AccountId;
+
+    fn to_owned(&self) -> Self::Owned {
+        AccountId(self.0.into())
+    }
+}
+
+impl<'a> From<&'a AccountIdRef> for AccountId {
+    fn from(id: &'a AccountIdRef) -> Self {
+        id.to_owned()
+    }
+}
+
+impl<'s> TryFrom<&'s str> for &'s AccountIdRef {
+    type Error = ParseAccountError;
+
+    fn try_from(value: &'s str) -> Result<Self, Self::Error> {
+        AccountIdRef::new(value)
+    }
+}
+
+impl<'s> TryFrom<&'s [u8]> for &'s AccountIdRef {
+    type Error = ParseAccountError;
+
+    fn try_from(value: &'s [u8]) -> Result<Self, Self::Error> {
+        AccountIdRef::from_bytes(value)
+    }
+}
+
+impl AsRef<str> for AccountIdRef {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<AccountIdRef> for String {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self == &other.0
+    }
+}
+
+impl PartialEq<String> for AccountIdRef {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<AccountIdRef> for str {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self == &other.0
+    }
+}
+
+impl PartialEq<str> for AccountIdRef {
+    fn eq(&self, other: &str) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<AccountIdRef> for &str {
     fn eq(&self, other: &AccountIdRef) -> bool {
+        *self == &other.0
+    }
+}
+
+impl<'a> PartialEq<&'a str> for AccountIdRef {
+    fn eq(&self, other: &&'a str) -> bool {
+        &self.0 == *other
+    }
+}
+
+impl<'a> PartialEq<&'a AccountIdRef> for str {
+    fn eq(&self, other: &&'a AccountIdRef) -> bool {
+        self == &other.0
+    }
+}
+
+impl PartialEq<str> for &AccountIdRef {
+    fn eq(&self, other: &str) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<'a> PartialEq<&'a AccountIdRef> for String {
+    fn eq(&self, other: &&'a AccountIdRef) -> bool {
         self == &other.0
     }
-}
+}
+
+impl PartialEq<String> for &AccountIdRef {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<[u8]> for AccountIdRef {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl PartialEq<AccountIdRef> for [u8] {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for AccountIdRef {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_bytes() == *other
+    }
+}
+
+impl PartialEq<AccountIdRef> for &[u8] {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        *self == other.as_bytes()
+    }
+}
+
+impl PartialOrd<[u8]> for AccountIdRef {
+    fn partial_cmp(&self, other: &[u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<AccountIdRef> for [u8] {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> PartialOrd<&'a [u8]> for AccountIdRef {
+    fn partial_cmp(&self, other: &&'a [u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<AccountIdRef> for &[u8] {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other.as_bytes())
+    }
+}
+
+impl PartialOrd<AccountIdRef> for String {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(&other.0)
+    }
+}
+
+impl PartialOrd<String> for AccountIdRef {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<AccountIdRef> for str {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<str> for AccountIdRef {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<AccountIdRef> for &str {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&other.as_str())
+    }
+}
+
+impl<'a> PartialOrd<&'a str> for AccountIdRef {
+    fn partial_cmp(&self, other: &&'a str) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(*other)
+    }
+}
+
+impl<'a> PartialOrd<&'a AccountIdRef> for String {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(&other.0)
+    }
+}
+
+impl PartialOrd<String> for &AccountIdRef {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other.as_str())
+    }
+}
+
+impl PartialEq<AccountIdRef> for Cow<'_, str> {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self.as_ref() == &other.0
+    }
+}
+
+impl PartialEq<Cow<'_, str>> for AccountIdRef {
+    fn eq(&self, other: &Cow<'_, str>) -> bool {
+        &self.0 == other.as_ref()
+    }
+}
+
+impl<'a> PartialEq<&'a AccountIdRef> for Cow<'_, str> {
+    fn eq(&self, other: &&'a AccountIdRef) -> bool {
+        self.as_ref() == &other.0
+    }
+}
+
+impl PartialEq<Cow<'_, str>> for &AccountIdRef {
+    fn eq(&self, other: &Cow<'_, str>) -> bool {
+        &self.0 == other.as_ref()
+    }
+}
+
+impl PartialOrd<AccountIdRef> for Cow<'_, str> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(&other.0)
+    }
+}
+
+impl PartialOrd<Cow<'_, str>> for AccountIdRef {
+    fn partial_cmp(&self, other: &Cow<'_, str>) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other.as_ref())
+    }
+}
+
+impl<'a> PartialOrd<&'a AccountIdRef> for Cow<'_, str> {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(&other.0)
+    }
+}
+
+impl PartialOrd<Cow<'_, str>> for &AccountIdRef {
+    fn partial_cmp(&self, other: &Cow<'_, str>) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other.as_ref())
+    }
+}
+
+impl PartialEq<AccountIdRef> for Box<str> {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self.as_ref() == &other.0
+    }
+}
+
+impl PartialEq<Box<str>> for AccountIdRef {
+    fn eq(&self, other: &Box<str>) -> bool {
+        &self.0 == other.as_ref()
+    }
+}
+
+impl<'a> PartialEq<&'a AccountIdRef> for Box<str> {
+    fn eq(&self, other: &&'a AccountIdRef) -> bool {
+        self.as_ref() == &other.0
+    }
+}
+
+impl PartialEq<Box<str>> for &AccountIdRef {
+    fn eq(&self, other: &Box<str>) -> bool {
+        &self.0 == other.as_ref()
+    }
+}
+
+impl PartialOrd<AccountIdRef> for Box<str> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(&other.0)
+    }
+}
+
+impl PartialOrd<Box<str>> for AccountIdRef {
+    fn partial_cmp(&self, other: &Box<str>) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other.as_ref())
+    }
+}
+
+impl<'a> PartialOrd<&'a AccountIdRef> for Box<str> {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.as_ref().partial_cmp(&other.0)
+    }
+}
+
+impl PartialOrd<Box<str>> for &AccountIdRef {
+    fn partial_cmp(&self, other: &Box<str>) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other.as_ref())
+    }
+}
+
+impl<'a> PartialOrd<&'a AccountIdRef> for str {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<str> for &AccountIdRef {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other)
+    }
+}
+
+impl<'a> From<&'a AccountIdRef> for Cow<'a, AccountIdRef> {
+    fn from(value: &'a AccountIdRef) -> Self {
+        Cow::Borrowed(value)
+    }
+}
+
+impl<'a> From<&'a AccountIdRef> for Cow<'a, str> {
+    fn from(value: &'a AccountIdRef) -> Self {
+        Cow::Borrowed(value.as_str())
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for &'a AccountIdRef {
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (crate::validation::MIN_LEN, Some(crate::validation::MAX_LEN))
+    }
+
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut s = u.arbitrary::<&str>()?;
+
+        // Truncating at the first violation, instead of rejecting the input, keeps the generated
+        // Account ID a prefix of the input. So as a fuzzer minimizes its input, the Account ID
+        // shrinks along with it, down to a 2-character top-level account.
+
+        loop {
+            match AccountIdRef::new(s) {
+                Ok(account_id) => break Ok(account_id),
+                Err(ParseAccountError {
+                    char: Some((idx, _)),
+                    ..
+                }) => {
+                    // `idx` is a byte offset, and every byte before it is ASCII, so this
+                    // always slices on a char boundary, even if a multibyte char follows.
+                    s = &s[..idx];
+                    continue;
+                }
+                _ => break Err(arbitrary::Error::IncorrectFormat),
+            }
+        }
+    }
+
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let s = <&str as arbitrary::Arbitrary>::arbitrary_take_rest(u)?;
+        AccountIdRef::new(s).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+/// Matches `account_id` against a pattern, see [`AccountIdRef::matches_pattern`].
+fn matches_labels(pattern: &str, account_id: &str) -> bool {
+    let (pattern_label, pattern_rest) = match pattern.split_once('.') {
+        Some((label, rest)) => (label, Some(rest)),
+        None => (pattern, None),
+    };
+
+    if pattern_label == "**" {
+        let Some(pattern_rest) = pattern_rest else {
+            return true;
+        };
+        // Try every split that leaves at least one label to `**`.
+        return account_id
+            .match_indices('.')
+            .any(|(idx, _)| matches_labels(pattern_rest, &account_id[idx + 1..]));
+    }
+
+    let (label, rest) = match account_id.split_once('.') {
+        Some((label, rest)) => (label, Some(rest)),
+        None => (account_id, None),
+    };
+    if !matches_label(pattern_label.as_bytes(), label.as_bytes()) {
+        return false;
+    }
+    match (pattern_rest, rest) {
+        (None, None) => true,
+        (Some(pattern_rest), Some(rest)) => matches_labels(pattern_rest, rest),
+        _ => false,
+    }
+}
+
+/// Matches a single label against a pattern where `*` matches any run of characters.
+fn matches_label(pattern: &[u8], label: &[u8]) -> bool {
+    let (mut p, mut l) = (0, 0);
+    // Where to resume after the last `*` if the rest doesn't match.
+    let mut backtrack = None;
+
+    while l < label.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, l));
+                p += 1;
+            }
+            Some(&c) if c == label[l] => {
+                p += 1;
+                l += 1;
+            }
+            _ => match backtrack {
+                Some((star, start)) => {
+                    p = star + 1;
+                    l = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "schemars")]
+    fn test_schemars() {
+        let schema = schemars::schema_for!(AccountIdRef);
+        let json_schema = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            json_schema,
+            serde_json::json!({
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "description": "Account identifier. This is the human readable UTF-8 string which is used internally to index accounts on the network and their respective state.\n\nThis is the \"referenced\" version of the account ID. It is to [`AccountId`] what [`str`] is to [`String`], and works quite similarly to [`Path`]. Like with [`str`] and [`Path`], you can't have a value of type `AccountIdRef`, but you can have a reference like `&AccountIdRef` or `&mut AccountIdRef`.\n\nThis type supports zero-copy deserialization offered by [`serde`](https://docs.rs/serde/), but cannot do the same for [`borsh`](https://docs.rs/borsh/) since the latter does not support zero-copy.\n\n# Examples ``` use near_account_id::{AccountId, AccountIdRef}; use std::convert::{TryFrom, TryInto};\n\n// Construction let alice = AccountIdRef::new(\"alice.near\").unwrap(); assert!(AccountIdRef::new(\"invalid.\").is_err()); ```\n\n[`FromStr`]: std::str::FromStr [`Path`]: std::path::Path",
+                    "title": "AccountIdRef",
+                    "type": "string",
+                    "minLength": 2,
+                    "maxLength": 64,
+                    "pattern": "^(([a-z\\d]+[-_])*[a-z\\d]+\\.)*([a-z\\d]+[-_])*[a-z\\d]+$"
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_err_kind_classification() {
+        let id = AccountIdRef::new("ErinMoriarty.near");
+        debug_assert!(
+            matches!(
+                id,
+                Err(ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((0, 'E'))
+                })
+            ),
+            "{:?}",
+            id
+        );
+
+        let id = AccountIdRef::new("-KarlUrban.near");
+        debug_assert!(
+            matches!(
+                id,
+                Err(ParseAccountError {
+                    kind: ParseErrorKind::RedundantSeparator,
+                    char: Some((0, '-'))
+                })
+            ),
+            "{:?}",
+            id
+        );
+
+        let id = AccountIdRef::new("anthonystarr.");
+        debug_assert!(
+            matches!(
+                id,
+                Err(ParseAccountError {
+                    kind: ParseErrorKind::RedundantSeparator,
+                    char: Some((12, '.'))
+                })
+            ),
+            "{:?}",
+            id
+        );
+
+        let id = AccountIdRef::new("jack__Quaid.near");
+        debug_assert!(
+            matches!(
+                id,
+                Err(ParseAccountError {
+                    kind: ParseErrorKind::RedundantSeparator,
+                    char: Some((5, '_'))
+                })
+            ),
+            "{:?}",
+            id
+        );
+    }
+
+    #[test]
+    fn test_is_valid_top_level_account_id() {
+        let ok_top_level_account_ids = &[
+            "aa",
+            "a-a",
+            "a-aa",
+            "100",
+            "0o",
+            "com",
+            "near",
+            "bowen",
+            "b-o_w_e-n",
+            "0o0ooo00oo00o",
+            "alex-skidanov",
+            "b-o_w_e-n",
+            "no_lols",
+            // ETH-implicit account
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            // NEAR-implicit account
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        ];
+        for account_id in ok_top_level_account_ids {
+            assert!(
+                AccountIdRef::new(account_id).map_or(false, |account_id| account_id.is_top_level()),
+                "Valid top level account id {:?} marked invalid",
+                account_id
+            );
+        }
+
+        let bad_top_level_account_ids = &[
+            "ƒelicia.near", // fancy ƒ!
+            "near.a",
+            "b.owen",
+            "bro.wen",
+            "a.ha",
+            "a.b-a.ra",
+            "some-complex-address@gmail.com",
+            "sub.buy_d1gitz@atata@b0-rg.c_0_m",
+            "over.9000",
+            "google.com",
+            "illia.cheapaccounts.near",
+            "10-4.8-2",
+            "a",
+            "A",
+            "Abc",
+            "-near",
+            "near-",
+            "-near-",
+            "near.",
+            ".near",
+            "near@",
+            "@near",
+            "неар",
+            "@@@@@",
+            "0__0",
+            "0_-_0",
+            "0_-_0",
+            "..",
+            "a..near",
+            "nEar",
+            "_bowen",
+            "hello world",
+            "abcdefghijklmnopqrstuvwxyz.abcdefghijklmnopqrstuvwxyz.abcdefghijklmnopqrstuvwxyz",
+            "01234567890123456789012345678901234567890123456789012345678901234",
+            // Valid regex and length, but reserved
+            "system",
+        ];
+        for account_id in bad_top_level_account_ids {
+            assert!(
+                !AccountIdRef::new(account_id)
+                    .map_or(false, |account_id| account_id.is_top_level()),
+                "Invalid top level account id {:?} marked valid",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_valid_sub_account_id() {
+        let ok_pairs = &[
+            ("test", "a.test"),
+            ("test-me", "abc.test-me"),
+            ("gmail.com", "abc.gmail.com"),
+            ("gmail.com", "abc-lol.gmail.com"),
+            ("gmail.com", "abc_lol.gmail.com"),
+            ("gmail.com", "bro-abc_lol.gmail.com"),
+            ("g0", "0g.g0"),
+            ("1g", "1g.1g"),
+            ("5-3", "4_2.5-3"),
+        ];
+        for (signer_id, sub_account_id) in ok_pairs {
+            assert!(
+                matches!(
+                    (AccountIdRef::new(signer_id), AccountIdRef::new(sub_account_id)),
+                    (Ok(signer_id), Ok(sub_account_id)) if sub_account_id.is_sub_account_of(signer_id)
+                ),
+                "Failed to create sub-account {:?} by account {:?}",
+                sub_account_id,
+                signer_id
+            );
+        }
+
+        let bad_pairs = &[
+            ("test", ".test"),
+            ("test", "test"),
+            ("test", "a1.a.test"),
+            ("test", "est"),
+            ("test", ""),
+            ("test", "st"),
+            ("test5", "ббб"),
+            ("test", "a-test"),
+            ("test", "etest"),
+            ("test", "a.etest"),
+            ("test", "retest"),
+            ("test-me", "abc-.test-me"),
+            ("test-me", "Abc.test-me"),
+            ("test-me", "-abc.test-me"),
+            ("test-me", "a--c.test-me"),
+            ("test-me", "a_-c.test-me"),
+            ("test-me", "a-_c.test-me"),
+            ("test-me", "_abc.test-me"),
+            ("test-me", "abc_.test-me"),
+            ("test-me", "..test-me"),
+            ("test-me", "a..test-me"),
+            ("gmail.com", "a.abc@gmail.com"),
+            ("gmail.com", ".abc@gmail.com"),
+            ("gmail.com", ".abc@gmail@com"),
+            ("gmail.com", "abc@gmail@com"),
+            ("test", "a@test"),
+            ("test_me", "abc@test_me"),
+            ("gmail.com", "abc@gmail.com"),
+            ("gmail@com", "abc.gmail@com"),
+            ("gmail.com", "abc-lol@gmail.com"),
+            ("gmail@com", "abc_lol.gmail@com"),
+            ("gmail@com", "bro-abc_lol.gmail@com"),
+            (
+                "gmail.com",
+                "123456789012345678901234567890123456789012345678901234567890@gmail.com",
+            ),
+            (
+                "123456789012345678901234567890123456789012345678901234567890",
+                "1234567890.123456789012345678901234567890123456789012345678901234567890",
+            ),
+            (
+                "b794f5ea0ba39494ce839613fffba74279579268",
+                // ETH-implicit account
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            ("aa", "ъ@aa"),
+            ("aa", "ъ.aa"),
+        ];
+        for (signer_id, sub_account_id) in bad_pairs {
+            assert!(
+                !matches!(
+                    (AccountIdRef::new(signer_id), AccountIdRef::new(sub_account_id)),
+                    (Ok(signer_id), Ok(sub_account_id)) if sub_account_id.is_sub_account_of(signer_id)
+                ),
+                "Invalid sub-account {:?} created by account {:?}",
+                sub_account_id,
+                signer_id
+            );
+        }
+    }
 
-impl PartialEq<str> for AccountIdRef {
-    fn eq(&self, other: &str) -> bool {
-        &self.0 == other
+    #[test]
+    fn test_is_account_id_near_implicit() {
+        let valid_near_implicit_account_ids = &[
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "6174617461746174617461746174617461746174617461746174617461746174",
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "20782e20662e64666420482123494b6b6c677573646b6c66676a646b6c736667",
+        ];
+        for valid_account_id in valid_near_implicit_account_ids {
+            assert!(
+                matches!(
+                    AccountIdRef::new(valid_account_id),
+                    Ok(account_id) if account_id.get_account_type() == AccountType::NearImplicitAccount
+                ),
+                "Account ID {} should be valid 64-len hex",
+                valid_account_id
+            );
+        }
+
+        let invalid_near_implicit_account_ids = &[
+            "000000000000000000000000000000000000000000000000000000000000000",
+            "6.74617461746174617461746174617461746174617461746174617461746174",
+            "012-456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            "fffff_ffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "oooooooooooooooooooooooooooooooooooooooooooooooooooooooooooooooo",
+            "00000000000000000000000000000000000000000000000000000000000000",
+        ];
+        for invalid_account_id in invalid_near_implicit_account_ids {
+            assert!(
+                !matches!(
+                    AccountIdRef::new(invalid_account_id),
+                    Ok(account_id) if account_id.get_account_type() == AccountType::NearImplicitAccount
+                ),
+                "Account ID {} is not a NEAR-implicit account",
+                invalid_account_id
+            );
+        }
     }
-}
 
-impl<'a> PartialEq<AccountIdRef> for &'a str {
-    fn eq(&self, other: &AccountIdRef) -> bool {
-        *self == &other.0
+    #[test]
+    fn test_is_account_id_eth_implicit() {
+        let valid_eth_implicit_account_ids = &[
+            "0x0000000000000000000000000000000000000000",
+            "0x6174617461746174617461746174617461746174",
+            "0x0123456789abcdef0123456789abcdef01234567",
+            "0xffffffffffffffffffffffffffffffffffffffff",
+            "0x20782e20662e64666420482123494b6b6c677573",
+        ];
+        for valid_account_id in valid_eth_implicit_account_ids {
+            assert!(
+                matches!(
+                    valid_account_id.parse::<AccountId>(),
+                    Ok(account_id) if account_id.get_account_type() == AccountType::EthImplicitAccount
+                ),
+                "Account ID {} should be valid 42-len hex, starting with 0x",
+                valid_account_id
+            );
+        }
+
+        let invalid_eth_implicit_account_ids = &[
+            "04b794f5ea0ba39494ce839613fffba74279579268",
+            "0x000000000000000000000000000000000000000",
+            "0x6.74617461746174617461746174617461746174",
+            "0x012-456789abcdef0123456789abcdef01234567",
+            "0xfffff_ffffffffffffffffffffffffffffffffff",
+            "0xoooooooooooooooooooooooooooooooooooooooo",
+            "0x00000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ];
+        for invalid_account_id in invalid_eth_implicit_account_ids {
+            assert!(
+                !matches!(
+                    invalid_account_id.parse::<AccountId>(),
+                    Ok(account_id) if account_id.get_account_type() == AccountType::EthImplicitAccount
+                ),
+                "Account ID {} is not an ETH-implicit account",
+                invalid_account_id
+            );
+        }
     }
-}
 
-impl<'a> PartialEq<&'a str> for AccountIdRef {
-    fn eq(&self, other: &&'a str) -> bool {
-        &self.0 == *other
+    #[test]
+    fn test_tokens() {
+        use Token::{Label, Separator};
+
+        let tokens = |account_id| {
+            AccountIdRef::new_or_panic(account_id)
+                .tokens()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(tokens("near"), [Label("near")]);
+        assert_eq!(
+            tokens("alice-b.near"),
+            [
+                Label("alice"),
+                Separator('-'),
+                Label("b"),
+                Separator('.'),
+                Label("near")
+            ]
+        );
+        assert_eq!(
+            tokens("b-o_w_e-n"),
+            [
+                Label("b"),
+                Separator('-'),
+                Label("o"),
+                Separator('_'),
+                Label("w"),
+                Separator('_'),
+                Label("e"),
+                Separator('-'),
+                Label("n")
+            ]
+        );
+
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let rendered: String = tokens(account_id)
+                .into_iter()
+                .map(|token| match token {
+                    Label(label) => label.to_string(),
+                    Separator(separator) => separator.to_string(),
+                })
+                .collect();
+            assert_eq!(rendered, account_id);
+        }
     }
-}
 
-impl<'a> PartialEq<&'a AccountIdRef> for str {
-    fn eq(&self, other: &&'a AccountIdRef) -> bool {
-        self == &other.0
+    #[test]
+    fn test_zero_implicit() {
+        let zero = AccountIdRef::ZERO_IMPLICIT;
+        assert_eq!(zero.len(), 64);
+        assert!(zero.as_bytes().iter().all(|&b| b == b'0'));
+        assert_eq!(zero.get_account_type(), AccountType::NearImplicitAccount);
     }
-}
 
-impl<'a> PartialEq<str> for &'a AccountIdRef {
-    fn eq(&self, other: &str) -> bool {
-        &self.0 == other
+    #[test]
+    fn test_is_registrar_controlled_tla() {
+        for account_id in ["near", "aa", "testnet", "0x", &"a".repeat(31)] {
+            assert!(
+                AccountIdRef::new_or_panic(account_id).is_registrar_controlled_tla(),
+                "{:?}",
+                account_id
+            );
+        }
+
+        for account_id in [
+            "alice.near",
+            "system",
+            &"a".repeat(32),
+            &"a".repeat(64),
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "0s7794f5ea0ba39494ce839613fffba74279579268",
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        ] {
+            assert!(
+                !AccountIdRef::new_or_panic(account_id).is_registrar_controlled_tla(),
+                "{:?}",
+                account_id
+            );
+        }
+
+        let long_tla = AccountId::parse_with_limits(&"a".repeat(100), 128).unwrap();
+        assert!(long_tla.is_registrar_controlled_tla());
+        let long_tla = AccountId::parse_with_limits(&"a".repeat(65), 128).unwrap();
+        assert!(long_tla.is_registrar_controlled_tla());
+        let long_sub_account = AccountId::parse_with_limits(&("a.".repeat(40) + "near"), 128);
+        assert!(!long_sub_account.unwrap().is_registrar_controlled_tla());
     }
-}
 
-impl<'a> PartialEq<&'a AccountIdRef> for String {
-    fn eq(&self, other: &&'a AccountIdRef) -> bool {
-        self == &other.0
+    #[test]
+    fn test_is_second_level() {
+        for account_id in ["alice.near", "a.b", "0x.near", "app.testnet"] {
+            assert!(
+                AccountIdRef::new_or_panic(account_id).is_second_level(),
+                "{:?}",
+                account_id
+            );
+        }
+
+        for account_id in [
+            "near",
+            "system",
+            "app.alice.near",
+            "v1.app.alice.near",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        ] {
+            assert!(
+                !AccountIdRef::new_or_panic(account_id).is_second_level(),
+                "{:?}",
+                account_id
+            );
+        }
     }
-}
 
-impl<'a> PartialEq<String> for &'a AccountIdRef {
-    fn eq(&self, other: &String) -> bool {
-        &self.0 == other
+    #[test]
+    fn test_bytes_comparisons() {
+        use core::cmp::Ordering;
+
+        let alice = AccountIdRef::new_or_panic("alice.near");
+
+        let bytes: &[u8] = b"alice.near";
+        assert_eq!(*alice, *bytes);
+        assert_eq!(*bytes, *alice);
+        assert_eq!(*alice, bytes);
+        assert_eq!(bytes, *alice);
+        assert_eq!((*alice).partial_cmp(bytes), Some(Ordering::Equal));
+
+        for (bytes, ordering) in [
+            (&b"bob.near"[..], Ordering::Less),
+            (b"alice.near\xff", Ordering::Less),
+            (b"alice.nea", Ordering::Greater),
+        ] {
+            assert_ne!(*alice, *bytes);
+            assert_ne!(*bytes, *alice);
+            assert_ne!(*alice, bytes);
+            assert_ne!(bytes, *alice);
+            assert_eq!((*alice).partial_cmp(bytes), Some(ordering));
+            assert_eq!((*alice).partial_cmp(&bytes), Some(ordering));
+            assert_eq!(bytes.partial_cmp(alice), Some(ordering.reverse()));
+            assert_eq!((*bytes).partial_cmp(alice), Some(ordering.reverse()));
+        }
+    }
+
+    #[test]
+    fn test_cow_str_comparisons() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+
+        for cow in [
+            Cow::Borrowed("alice.near"),
+            Cow::Owned("alice.near".to_string()),
+        ] {
+            assert_eq!(alice, cow);
+            assert_eq!(cow, alice);
+            assert_eq!(*alice, cow);
+            assert_eq!(cow, *alice);
+            assert_eq!(alice.partial_cmp(&cow), Some(core::cmp::Ordering::Equal));
+        }
+
+        for cow in [
+            Cow::Borrowed("bob.near"),
+            Cow::Owned("bob.near".to_string()),
+        ] {
+            assert_ne!(alice, cow);
+            assert_ne!(cow, *alice);
+            assert!(alice < cow);
+            assert!(*alice < cow);
+            assert!(cow > alice);
+            assert!(cow > *alice);
+        }
+    }
+
+    #[test]
+    fn test_into_cow_str() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        let (cow, allocs) = crate::count_alloc::count_allocs(|| Cow::<str>::from(alice));
+        assert_eq!(allocs, 0);
+        assert!(matches!(cow, Cow::Borrowed(s) if s.as_ptr() == alice.as_str().as_ptr()));
+    }
+
+    #[test]
+    fn test_boxed_str_comparisons() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+
+        let boxed = Box::<str>::from("alice.near");
+        assert_eq!(alice, boxed);
+        assert_eq!(boxed, alice);
+        assert_eq!(*alice, boxed);
+        assert_eq!(boxed, *alice);
+        assert_eq!(alice.partial_cmp(&boxed), Some(core::cmp::Ordering::Equal));
+
+        let boxed = Box::<str>::from("bob.near");
+        assert_ne!(alice, boxed);
+        assert_ne!(boxed, *alice);
+        assert!(alice < boxed);
+        assert!(*alice < boxed);
+        assert!(boxed > alice);
+        assert!(boxed > *alice);
+    }
+
+    #[test]
+    fn test_truncated() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            if account_id.is_named() {
+                assert_eq!(account_id.truncated().to_string(), account_id.as_str());
+            }
+        }
+
+        let cases = [
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                "9879…d6de",
+            ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", "0xb7…9268"),
+            ("0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b", "0s7d…0a9b"),
+            ("alice.near", "alice.near"),
+            (
+                "a-very-long-named-account-id-that-is-not-implicit-at-all.near",
+                "a-very-long-named-account-id-that-is-not-implicit-at-all.near",
+            ),
+        ];
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(account_id.truncated().to_string(), expected);
+        }
+
+        let implicit = AccountIdRef::new_or_panic(cases[0].0);
+        let mut buf = String::with_capacity(64);
+        let (_, allocs) = crate::count_alloc::count_allocs(|| {
+            use core::fmt::Write;
+            write!(buf, "{}", implicit.truncated()).unwrap();
+        });
+        assert_eq!(allocs, 0);
+        assert_eq!(buf, "9879…d6de");
+    }
+
+    #[test]
+    fn test_account_type_round_trip() {
+        let account_types = [
+            AccountType::NamedAccount,
+            AccountType::NearImplicitAccount,
+            AccountType::EthImplicitAccount,
+            AccountType::NearDeterministicAccount,
+        ];
+        for account_type in account_types {
+            assert_eq!(account_type.to_string(), account_type.as_str());
+            assert_eq!(account_type.to_string().parse(), Ok(account_type));
+        }
+
+        for s in ["", "Named", "near_implicit", "NamedAccount", " named"] {
+            assert_eq!(s.parse::<AccountType>(), Err(ParseAccountTypeError(())));
+        }
+    }
+
+    #[test]
+    fn test_first_invalid() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let (first_invalid, allocs) =
+                crate::count_alloc::count_allocs(|| account_id.first_invalid());
+            assert_eq!(first_invalid, None);
+            assert_eq!(allocs, 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "internal_unstable")]
+    fn test_first_invalid_unvalidated() {
+        let cases = [
+            ("Alice.near", Some((0, 'A'))),
+            ("alice..near", Some((6, '.'))),
+            ("alice.near.", Some((10, '.'))),
+            ("ƒelicia.near", Some((0, 'ƒ'))),
+            ("bo🚀b.near", Some((2, '🚀'))),
+            (".", Some((0, '.'))),
+            ("a", None),
+            ("", None),
+        ];
+        for (account_id, expected) in cases {
+            #[allow(deprecated)]
+            let account_id = AccountId::new_unvalidated(account_id.to_string());
+            let (first_invalid, allocs) =
+                crate::count_alloc::count_allocs(|| account_id.first_invalid());
+            assert_eq!(first_invalid, expected, "{:?}", account_id);
+            assert_eq!(allocs, 0);
+        }
+
+        // Characters are still checked past `MAX_LEN`.
+        #[allow(deprecated)]
+        let account_id = AccountId::new_unvalidated("a".repeat(100) + ".Near");
+        assert_eq!(account_id.first_invalid(), Some((101, 'N')));
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_fingerprint128() {
+        // The XXH3-128 reference vector for empty input.
+        assert_eq!(
+            xxhash_rust::xxh3::xxh3_128(b""),
+            0x99aa06d3014798d86001c324468d497f
+        );
+
+        let cases = [
+            ("alice.near", 0x3ad6aaa7b05380ef4e7cb14ac398f722),
+            ("bob.near", 0x6ccf79fd2aed2603b463f76a42d5e0af),
+            ("near", 0xb9a870de3ea8877c111ac51ba4eee828),
+        ];
+        for (account_id, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id).fingerprint128(),
+                expected,
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "hash")]
+    fn test_hash_bytes() {
+        let cases = [
+            (
+                "alice.near",
+                "2dd5dda540767b3a1aa33544bcba38042f4df6de9bddb46798b29481c842c558",
+            ),
+            (
+                "bob.near",
+                "24341428553285e10e74a5f26f4638ac53afb28c032aff1a04900e6eb115a404",
+            ),
+            (
+                "near",
+                "46ba34770bccfde756708d47d83fb7c8257fe8a4b3a35f25d385a8284021f476",
+            ),
+        ];
+        for (account_id, expected) in cases {
+            let mut hash = String::new();
+            crate::hex::encode(
+                &AccountIdRef::new_or_panic(account_id).hash_bytes(),
+                &mut hash,
+            );
+            assert_eq!(hash, expected, "{:?}", account_id);
+        }
     }
-}
 
-impl PartialOrd<AccountIdRef> for String {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(&other.0)
+    #[test]
+    #[cfg(feature = "hash")]
+    fn test_shard_id() {
+        let cases = [
+            ("alice.near", [0, 1, 1, 5]),
+            ("bob.near", [0, 0, 0, 4]),
+            ("near", [0, 0, 2, 6]),
+            ("system", [0, 1, 3, 3]),
+        ];
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let shard_ids = [1, 2, 4, 8].map(|num_shards| account_id.shard_id(num_shards));
+            assert_eq!(shard_ids, expected, "{:?}", account_id);
+        }
     }
-}
 
-impl PartialOrd<String> for AccountIdRef {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(other.as_str())
-    }
-}
+    #[test]
+    #[cfg(feature = "eip55")]
+    fn test_to_eip55() {
+        // Test vectors from EIP-55.
+        let cases = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for expected in cases {
+            let lowercase = expected.to_ascii_lowercase();
+            let account_id = AccountIdRef::new(&lowercase).unwrap();
+            assert_eq!(account_id.to_eip55().unwrap(), expected);
+            assert!(AccountIdRef::new(expected).is_err());
+        }
 
-impl PartialOrd<AccountIdRef> for str {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
-        self.partial_cmp(other.as_str())
+        for account_id in [
+            "alice.near",
+            &"ab".repeat(32),
+            &("0s".to_string() + &"ab".repeat(20)),
+        ] {
+            assert_eq!(AccountIdRef::new_or_panic(account_id).to_eip55(), None);
+        }
     }
-}
 
-impl PartialOrd<str> for AccountIdRef {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(other)
-    }
-}
+    #[test]
+    fn test_split_labels() {
+        let cases = [
+            ("near", ("near", None), (None, "near")),
+            (
+                "alice.near",
+                ("alice", Some("near")),
+                (Some("alice"), "near"),
+            ),
+            (
+                "app.alice.near",
+                ("app", Some("alice.near")),
+                (Some("app.alice"), "near"),
+            ),
+            ("a.near", ("a", Some("near")), (None, "near")),
+            ("near.a", ("near", None), (Some("near"), "a")),
+            ("a.b.c", ("a", Some("b.c")), (Some("a.b"), "c")),
+        ];
+        for (account_id, first, last) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
 
-impl<'a> PartialOrd<AccountIdRef> for &'a str {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
-        self.partial_cmp(&other.as_str())
-    }
-}
+            let (label, rest) = account_id.split_first_label();
+            assert_eq!((label, rest.map(AccountIdRef::as_str)), first);
 
-impl<'a> PartialOrd<&'a str> for AccountIdRef {
-    fn partial_cmp(&self, other: &&'a str) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(*other)
+            let (rest, label) = account_id.split_last_label();
+            assert_eq!((rest.map(AccountIdRef::as_str), label), last);
+            assert_eq!(Some(label), account_id.labels().next_back());
+        }
     }
-}
 
-impl<'a> PartialOrd<&'a AccountIdRef> for String {
-    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(&other.0)
+    #[test]
+    fn test_labels() {
+        let cases: [(&str, &[&str]); 4] = [
+            ("near", &["near"]),
+            ("alice.near", &["alice", "near"]),
+            ("app_1.alice-b.near", &["app_1", "alice-b", "near"]),
+            ("a.b.c.d", &["a", "b", "c", "d"]),
+        ];
+        for (account_id, labels) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert!(account_id.labels().eq(labels.iter().copied()));
+            assert!(account_id.labels().rev().eq(labels.iter().rev().copied()));
+            assert_eq!(
+                AccountId::from_labels(account_id.labels()).unwrap(),
+                account_id
+            );
+        }
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(
+                AccountId::from_labels(account_id.labels()).unwrap(),
+                account_id
+            );
+        }
     }
-}
 
-impl<'a> PartialOrd<String> for &'a AccountIdRef {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(other.as_str())
-    }
-}
+    #[test]
+    fn test_remaining_capacity() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        assert_eq!(alice.remaining_capacity(), 54);
 
-impl<'a> PartialOrd<&'a AccountIdRef> for str {
-    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<std::cmp::Ordering> {
-        self.partial_cmp(other.as_str())
-    }
-}
+        let max = "a".repeat(AccountId::MAX_LEN);
+        assert_eq!(AccountIdRef::new_or_panic(&max).remaining_capacity(), 0);
 
-impl<'a> PartialOrd<str> for &'a AccountIdRef {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
-        self.as_str().partial_cmp(other)
-    }
-}
+        let long_tla = AccountId::parse_with_limits(&"a".repeat(100), 128).unwrap();
+        assert_eq!(long_tla.remaining_capacity(), 0);
+        assert!(!long_tla.can_prepend_label(""));
 
-impl<'a> From<&'a AccountIdRef> for Cow<'a, AccountIdRef> {
-    fn from(value: &'a AccountIdRef) -> Self {
-        Cow::Borrowed(value)
+        for len in 0..AccountId::MAX_LEN {
+            let label = "a".repeat(len);
+            assert_eq!(
+                alice.can_prepend_label(&label),
+                alice.make_sub_account(&label).map_err(|err| err.kind)
+                    != Err(ParseErrorKind::TooLong),
+                "{}",
+                len
+            );
+            assert_eq!(
+                alice.can_prepend_label(&label),
+                label.len() + 1 + alice.len() <= AccountId::MAX_LEN
+            );
+        }
     }
-}
 
-#[cfg(feature = "arbitrary")]
-impl<'a> arbitrary::Arbitrary<'a> for &'a AccountIdRef {
-    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
-        (crate::validation::MIN_LEN, Some(crate::validation::MAX_LEN))
+    #[test]
+    fn test_make_sub_account() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+
+        let (app, allocs) = crate::count_alloc::count_allocs(|| alice.make_sub_account("app"));
+        assert_eq!(app.unwrap(), "app.alice.near");
+        assert_eq!(allocs, 1);
+
+        let max_label = "a".repeat(AccountId::MAX_LEN - alice.len() - 1);
+        let max = alice.make_sub_account(&max_label).unwrap();
+        assert_eq!(max.len(), AccountId::MAX_LEN);
+        assert_eq!(max.get_parent_account_id().unwrap(), alice);
+
+        let cases = [
+            (max_label.clone() + "a", ParseErrorKind::TooLong, None),
+            (max_label + "A", ParseErrorKind::TooLong, None),
+            ("".to_string(), ParseErrorKind::TooShort, None),
+            (
+                "app-".to_string(),
+                ParseErrorKind::RedundantSeparator,
+                Some((3, '-')),
+            ),
+            (
+                "app.v2".to_string(),
+                ParseErrorKind::InvalidChar,
+                Some((3, '.')),
+            ),
+            (
+                "App".to_string(),
+                ParseErrorKind::InvalidChar,
+                Some((0, 'A')),
+            ),
+        ];
+        for (label, kind, char) in cases {
+            assert_eq!(
+                alice.make_sub_account(&label),
+                Err(ParseAccountError { kind, char }),
+                "{:?}",
+                label
+            );
+        }
     }
 
-    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let mut s = u.arbitrary::<&str>()?;
-
-        loop {
-            match AccountIdRef::new(s) {
-                Ok(account_id) => break Ok(account_id),
-                Err(ParseAccountError {
-                    char: Some((idx, _)),
-                    ..
-                }) => {
-                    s = &s[..idx];
-                    continue;
-                }
-                _ => break Err(arbitrary::Error::IncorrectFormat),
-            }
+    #[test]
+    fn test_common_ancestor() {
+        let near_implicit = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+        let other_near_implicit =
+            "00793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+        let cases = [
+            ("a.app.near", "b.app.near", Some("app.near")),
+            ("a.app.near", "app.near", Some("app.near")),
+            ("app.near", "app.near", Some("app.near")),
+            ("a.app.near", "capp.near", Some("near")),
+            ("app.near", "capp.near", Some("near")),
+            ("a_b.near", "c_b.near", Some("near")),
+            ("alice.near", "bob.near", Some("near")),
+            ("alice.near", "alice.testnet", None),
+            ("near", "anear", None),
+            ("a.x", "b.x", None),
+            ("a.b.x", "c.b.x", Some("b.x")),
+            (near_implicit, near_implicit, Some(near_implicit)),
+            (near_implicit, other_near_implicit, None),
+            (near_implicit, "alice.near", None),
+        ];
+        for (a, b, expected) in cases {
+            let a = AccountIdRef::new_or_panic(a);
+            let b = AccountIdRef::new_or_panic(b);
+            assert_eq!(
+                a.common_ancestor(b).map(AccountIdRef::as_str),
+                expected,
+                "{} and {}",
+                a,
+                b
+            );
+            assert_eq!(
+                b.common_ancestor(a).map(AccountIdRef::as_str),
+                expected,
+                "{} and {}",
+                b,
+                a
+            );
         }
     }
 
-    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let s = <&str as arbitrary::Arbitrary>::arbitrary_take_rest(u)?;
-        AccountIdRef::new(s).map_err(|_| arbitrary::Error::IncorrectFormat)
+    #[test]
+    fn test_ends_with_labels() {
+        let cases = [
+            ("x.pool.near", "pool.near", true),
+            ("x.pool.near", "near", true),
+            ("x.pool.near", "x.pool.near", true),
+            ("x.pool.near", "ool.near", false),
+            ("x.pool.near", "pool", false),
+            ("x.pool.near", "y.x.pool.near", false),
+            ("near", "near", true),
+            ("xnear", "near", false),
+            ("x_near", "near", false),
+        ];
+        for (account_id, suffix, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id)
+                    .ends_with_labels(AccountIdRef::new_or_panic(suffix)),
+                expected,
+                "{:?} ends with {:?}",
+                account_id,
+                suffix
+            );
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::ParseErrorKind;
 
-    use super::*;
+    #[test]
+    fn test_strip_suffix_account() {
+        let cases = [
+            ("app.alice.near", "near", Some("app.alice")),
+            ("app.alice.near", "alice.near", Some("app")),
+            ("app.alice.near", "app.alice.near", None),
+            ("app.alice.near", "e.near", None),
+            ("offnear.near", "near", Some("offnear")),
+            ("offnear", "near", None),
+            ("off_near", "near", None),
+            ("near", "app.near", None),
+        ];
+        for (account_id, ancestor, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id)
+                    .strip_suffix_account(AccountIdRef::new_or_panic(ancestor)),
+                expected,
+                "{:?} stripped of {:?}",
+                account_id,
+                ancestor
+            );
+        }
+    }
 
     #[test]
-    #[cfg(feature = "schemars")]
-    fn test_schemars() {
-        let schema = schemars::schema_for!(AccountIdRef);
-        let json_schema = serde_json::to_value(&schema).unwrap();
+    fn test_max_labels() {
+        let deepest = ["a"; AccountIdRef::MAX_LABELS].join(".");
+        let deepest = AccountIdRef::new_or_panic(&deepest);
+        assert_eq!(deepest.labels().count(), AccountIdRef::MAX_LABELS);
+
+        let too_deep = ["a"; AccountIdRef::MAX_LABELS + 1].join(".");
         assert_eq!(
-            json_schema,
-            serde_json::json!({
-                    "$schema": "http://json-schema.org/draft-07/schema#",
-                    "description": "Account identifier. This is the human readable UTF-8 string which is used internally to index accounts on the network and their respective state.\n\nThis is the \"referenced\" version of the account ID. It is to [`AccountId`] what [`str`] is to [`String`], and works quite similarly to [`Path`]. Like with [`str`] and [`Path`], you can't have a value of type `AccountIdRef`, but you can have a reference like `&AccountIdRef` or `&mut AccountIdRef`.\n\nThis type supports zero-copy deserialization offered by [`serde`](https://docs.rs/serde/), but cannot do the same for [`borsh`](https://docs.rs/borsh/) since the latter does not support zero-copy.\n\n# Examples ``` use near_account_id::{AccountId, AccountIdRef}; use std::convert::{TryFrom, TryInto};\n\n// Construction let alice = AccountIdRef::new(\"alice.near\").unwrap(); assert!(AccountIdRef::new(\"invalid.\").is_err()); ```\n\n[`FromStr`]: std::str::FromStr [`Path`]: std::path::Path",
-                    "title": "AccountIdRef",
-                    "type": "string"
-                }
-            )
+            AccountIdRef::new(&too_deep).unwrap_err().kind(),
+            &ParseErrorKind::TooLong
         );
+
+        // The ancestors of the deepest Account ID are `max_possible_depth` parents deep. The walk
+        // is capped one past that, so an unbounded chain would fail the assertion instead of
+        // hanging the test.
+        let deepest = ["a"; AccountIdRef::MAX_LABELS - 1].join(".") + ".aa";
+        let deepest = AccountIdRef::new_or_panic(&deepest);
+        assert_eq!(deepest.len(), AccountIdRef::MAX_LEN);
+        let ancestors: Vec<_> =
+            core::iter::successors(deepest.get_parent_account_id(), |account_id| {
+                account_id.get_parent_account_id()
+            })
+            .take(AccountIdRef::max_possible_depth() + 1)
+            .collect();
+        assert_eq!(ancestors.len(), AccountIdRef::max_possible_depth());
+        assert_eq!(*ancestors.last().unwrap(), "aa");
+
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert!(account_id.labels().count() <= AccountIdRef::MAX_LABELS);
+        }
     }
 
     #[test]
-    fn test_err_kind_classification() {
-        let id = AccountIdRef::new("ErinMoriarty.near");
-        debug_assert!(
-            matches!(
-                id,
-                Err(ParseAccountError {
-                    kind: ParseErrorKind::InvalidChar,
-                    char: Some((0, 'E'))
-                })
-            ),
-            "{:?}",
-            id
-        );
-
-        let id = AccountIdRef::new("-KarlUrban.near");
-        debug_assert!(
-            matches!(
-                id,
-                Err(ParseAccountError {
-                    kind: ParseErrorKind::RedundantSeparator,
-                    char: Some((0, '-'))
-                })
-            ),
-            "{:?}",
-            id
+    fn test_labels_into() {
+        let deepest = ["a"; AccountIdRef::MAX_LABELS].join(".");
+        let deepest = AccountIdRef::new_or_panic(&deepest);
+        let ((labels, len), allocs) = crate::count_alloc::count_allocs(|| {
+            deepest
+                .labels_into::<{ AccountIdRef::MAX_LABELS }>()
+                .unwrap()
+        });
+        assert_eq!(allocs, 0);
+        assert_eq!(len, AccountIdRef::MAX_LABELS);
+        assert_eq!(labels, ["a"; AccountIdRef::MAX_LABELS]);
+        assert_eq!(
+            deepest.labels_into::<{ AccountIdRef::MAX_LABELS - 1 }>(),
+            Err(TooManyLabels(()))
         );
 
-        let id = AccountIdRef::new("anthonystarr.");
-        debug_assert!(
-            matches!(
-                id,
-                Err(ParseAccountError {
-                    kind: ParseErrorKind::RedundantSeparator,
-                    char: Some((12, '.'))
-                })
-            ),
-            "{:?}",
-            id
-        );
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let (labels, len) = account_id
+                .labels_into::<{ AccountIdRef::MAX_LABELS }>()
+                .unwrap();
+            assert!(labels[..len].iter().copied().eq(account_id.labels()));
+            assert!(labels[len..].iter().all(|label| label.is_empty()));
+        }
 
-        let id = AccountIdRef::new("jack__Quaid.near");
-        debug_assert!(
-            matches!(
-                id,
-                Err(ParseAccountError {
-                    kind: ParseErrorKind::RedundantSeparator,
-                    char: Some((5, '_'))
-                })
-            ),
-            "{:?}",
-            id
-        );
+        let near = AccountIdRef::new_or_panic("near");
+        assert_eq!(near.labels_into::<1>(), Ok((["near"], 1)));
+        assert_eq!(near.labels_into::<0>(), Err(TooManyLabels(())));
     }
 
     #[test]
-    fn test_is_valid_top_level_account_id() {
-        let ok_top_level_account_ids = &[
-            "aa",
-            "a-a",
-            "a-aa",
-            "100",
-            "0o",
-            "com",
-            "near",
-            "bowen",
-            "b-o_w_e-n",
-            "0o0ooo00oo00o",
-            "alex-skidanov",
-            "b-o_w_e-n",
-            "no_lols",
-            // ETH-implicit account
-            "0xb794f5ea0ba39494ce839613fffba74279579268",
-            // NEAR-implicit account
-            "0123456789012345678901234567890123456789012345678901234567890123",
+    fn test_levels_below() {
+        let cases = [
+            ("app.alice.near", "near", Some(2)),
+            ("app.alice.near", "alice.near", Some(1)),
+            ("alice.near", "near", Some(1)),
+            ("app.alice.near", "app.alice.near", None),
+            ("app.alice.near", "e.near", None),
+            ("app.alice.near", "bob.near", None),
+            ("offnear", "near", None),
+            ("near", "app.near", None),
         ];
-        for account_id in ok_top_level_account_ids {
-            assert!(
-                AccountIdRef::new(account_id).map_or(false, |account_id| account_id.is_top_level()),
-                "Valid top level account id {:?} marked invalid",
-                account_id
+        for (account_id, ancestor, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let ancestor = AccountIdRef::new_or_panic(ancestor);
+            assert_eq!(
+                account_id.levels_below(ancestor),
+                expected,
+                "{:?} below {:?}",
+                account_id,
+                ancestor
+            );
+            assert_eq!(
+                expected == Some(1),
+                account_id.is_sub_account_of(ancestor),
+                "{:?} below {:?}",
+                account_id,
+                ancestor
             );
         }
-
-        let bad_top_level_account_ids = &[
-            "ƒelicia.near", // fancy ƒ!
-            "near.a",
-            "b.owen",
-            "bro.wen",
-            "a.ha",
-            "a.b-a.ra",
-            "some-complex-address@gmail.com",
-            "sub.buy_d1gitz@atata@b0-rg.c_0_m",
-            "over.9000",
-            "google.com",
-            "illia.cheapaccounts.near",
-            "10-4.8-2",
-            "a",
-            "A",
-            "Abc",
-            "-near",
-            "near-",
-            "-near-",
-            "near.",
-            ".near",
-            "near@",
-            "@near",
-            "неар",
-            "@@@@@",
-            "0__0",
-            "0_-_0",
-            "0_-_0",
-            "..",
-            "a..near",
-            "nEar",
-            "_bowen",
-            "hello world",
-            "abcdefghijklmnopqrstuvwxyz.abcdefghijklmnopqrstuvwxyz.abcdefghijklmnopqrstuvwxyz",
-            "01234567890123456789012345678901234567890123456789012345678901234",
-            // Valid regex and length, but reserved
-            "system",
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        let cases = [
+            ("alice.near", "*.near", true),
+            ("app.alice.near", "*.near", false),
+            ("near", "*.near", false),
+            ("alice.near", "**.near", true),
+            ("app.alice.near", "**.near", true),
+            ("near", "**.near", false),
+            ("app.alice.near", "app.**", true),
+            ("app.near", "app.**", true),
+            ("app", "app.**", false),
+            ("application.near", "app.**", false),
+            ("app.alice.near", "app.*.near", true),
+            ("app.near", "app.*.near", false),
+            ("app.a.b.near", "app.*.near", false),
+            ("app.a.b.near", "app.**.near", true),
+            ("app.a.b.near", "**.b.**", true),
+            ("app.a.b.near", "**.a.b", false),
+            ("alice.near", "ali*.near", true),
+            ("ali.near", "ali*.near", true),
+            ("alice.near", "*ice*.near", true),
+            ("alice.near", "a*e.near", true),
+            ("alice.near", "a*x.near", false),
+            ("alice.near", "alice.near", true),
+            ("alice.near", "bob.near", false),
+            ("alice.near", "*", false),
+            ("alice", "*", true),
+            ("alice.near", "**", true),
+            ("alice.near", "", false),
+            ("alice.near", "alice.near.", false),
         ];
-        for account_id in bad_top_level_account_ids {
-            assert!(
-                !AccountIdRef::new(account_id)
-                    .map_or(false, |account_id| account_id.is_top_level()),
-                "Invalid top level account id {:?} marked valid",
-                account_id
+        for (account_id, pattern, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(
+                account_id.matches_pattern(pattern),
+                expected,
+                "{:?} against {:?}",
+                account_id,
+                pattern
             );
         }
     }
 
     #[test]
-    fn test_is_valid_sub_account_id() {
-        let ok_pairs = &[
-            ("test", "a.test"),
-            ("test-me", "abc.test-me"),
-            ("gmail.com", "abc.gmail.com"),
-            ("gmail.com", "abc-lol.gmail.com"),
-            ("gmail.com", "abc_lol.gmail.com"),
-            ("gmail.com", "bro-abc_lol.gmail.com"),
-            ("g0", "0g.g0"),
-            ("1g", "1g.1g"),
-            ("5-3", "4_2.5-3"),
+    fn test_starts_with_labels() {
+        let cases = [
+            ("app.alice.near", "app", true),
+            ("app.alice.near", "app.alice", true),
+            ("app.alice.near", "app.alice.near", true),
+            ("app.alice.near", "ap", false),
+            ("app.alice.near", "app.alice.near.x", false),
+            ("app_1.alice.near", "app", false),
+            ("app-1.alice.near", "app", false),
         ];
-        for (signer_id, sub_account_id) in ok_pairs {
-            assert!(
-                matches!(
-                    (AccountIdRef::new(signer_id), AccountIdRef::new(sub_account_id)),
-                    (Ok(signer_id), Ok(sub_account_id)) if sub_account_id.is_sub_account_of(signer_id)
-                ),
-                "Failed to create sub-account {:?} by account {:?}",
-                sub_account_id,
-                signer_id
+        for (account_id, prefix, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id)
+                    .starts_with_labels(AccountIdRef::new_or_panic(prefix)),
+                expected,
+                "{:?} starts with {:?}",
+                account_id,
+                prefix
             );
         }
+    }
 
-        let bad_pairs = &[
-            ("test", ".test"),
-            ("test", "test"),
-            ("test", "a1.a.test"),
-            ("test", "est"),
-            ("test", ""),
-            ("test", "st"),
-            ("test5", "ббб"),
-            ("test", "a-test"),
-            ("test", "etest"),
-            ("test", "a.etest"),
-            ("test", "retest"),
-            ("test-me", "abc-.test-me"),
-            ("test-me", "Abc.test-me"),
-            ("test-me", "-abc.test-me"),
-            ("test-me", "a--c.test-me"),
-            ("test-me", "a_-c.test-me"),
-            ("test-me", "a-_c.test-me"),
-            ("test-me", "_abc.test-me"),
-            ("test-me", "abc_.test-me"),
-            ("test-me", "..test-me"),
-            ("test-me", "a..test-me"),
-            ("gmail.com", "a.abc@gmail.com"),
-            ("gmail.com", ".abc@gmail.com"),
-            ("gmail.com", ".abc@gmail@com"),
-            ("gmail.com", "abc@gmail@com"),
-            ("test", "a@test"),
-            ("test_me", "abc@test_me"),
-            ("gmail.com", "abc@gmail.com"),
-            ("gmail@com", "abc.gmail@com"),
-            ("gmail.com", "abc-lol@gmail.com"),
-            ("gmail@com", "abc_lol.gmail@com"),
-            ("gmail@com", "bro-abc_lol.gmail@com"),
+    #[test]
+    fn test_registration_error() {
+        let cases = [
+            ("system", Some(RegistrationError::Reserved)),
+            ("alice.near", None),
+            ("near", None),
+            ("system.near", None),
+            ("systems", None),
             (
-                "gmail.com",
-                "123456789012345678901234567890123456789012345678901234567890@gmail.com",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                Some(RegistrationError::Implicit),
             ),
             (
-                "123456789012345678901234567890123456789012345678901234567890",
-                "1234567890.123456789012345678901234567890123456789012345678901234567890",
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                Some(RegistrationError::Implicit),
             ),
             (
-                "b794f5ea0ba39494ce839613fffba74279579268",
-                // ETH-implicit account
-                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                "0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b",
+                Some(RegistrationError::Implicit),
             ),
-            ("aa", "ъ@aa"),
-            ("aa", "ъ.aa"),
         ];
-        for (signer_id, sub_account_id) in bad_pairs {
-            assert!(
-                !matches!(
-                    (AccountIdRef::new(signer_id), AccountIdRef::new(sub_account_id)),
-                    (Ok(signer_id), Ok(sub_account_id)) if sub_account_id.is_sub_account_of(&signer_id)
-                ),
-                "Invalid sub-account {:?} created by account {:?}",
-                sub_account_id,
-                signer_id
+        for (account_id, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id).registration_error(),
+                expected,
+                "{:?}",
+                account_id
             );
         }
     }
 
     #[test]
-    fn test_is_account_id_near_implicit() {
-        let valid_near_implicit_account_ids = &[
-            "0000000000000000000000000000000000000000000000000000000000000000",
-            "6174617461746174617461746174617461746174617461746174617461746174",
-            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
-            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-            "20782e20662e64666420482123494b6b6c677573646b6c66676a646b6c736667",
+    fn test_depth_in_range() {
+        let cases = [
+            ("near", 1),
+            ("alice.near", 2),
+            ("app.alice.near", 3),
+            ("v1.app.alice.near", 4),
+            ("x.v1.app.alice.near", 5),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                1,
+            ),
         ];
-        for valid_account_id in valid_near_implicit_account_ids {
-            assert!(
-                matches!(
-                    AccountIdRef::new(valid_account_id),
-                    Ok(account_id) if account_id.get_account_type() == AccountType::NearImplicitAccount
-                ),
-                "Account ID {} should be valid 64-len hex",
-                valid_account_id
+        for (account_id, depth) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert!(account_id.depth_in_range(depth..=depth));
+            assert!(account_id.depth_in_range(1..=depth));
+            assert!(!account_id.depth_in_range(depth + 1..=depth + 2));
+            assert!(!account_id.depth_in_range(0..=depth - 1));
+        }
+
+        let in_range = |account_id| AccountIdRef::new_or_panic(account_id).depth_in_range(2..=4);
+        assert!(!in_range("near"));
+        assert!(in_range("alice.near"));
+        assert!(in_range("v1.app.alice.near"));
+        assert!(!in_range("x.v1.app.alice.near"));
+    }
+
+    #[test]
+    fn test_to_json_pointer_segment() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert_eq!(account_id.to_json_pointer_segment(), account_id.as_str());
+        }
+
+        let segment = AccountIdRef::new_or_panic("app.alice.near").to_json_pointer_segment();
+        assert_eq!(
+            format!("/accounts/{}/balance", segment),
+            "/accounts/app.alice.near/balance"
+        );
+    }
+
+    #[test]
+    fn test_with_prefix() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+
+        let (key, allocs) = crate::count_alloc::count_allocs(|| alice.with_prefix("acct:"));
+        assert_eq!(key, "acct:alice.near");
+        assert_eq!(key.capacity(), key.len());
+        assert_eq!(allocs, 1);
+
+        assert_eq!(alice.with_prefix(""), "alice.near");
+    }
+
+    #[test]
+    fn test_account_type_predicates() {
+        let cases = [
+            ("alice.near", AccountType::NamedAccount),
+            ("near", AccountType::NamedAccount),
+            ("0x", AccountType::NamedAccount),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                AccountType::EthImplicitAccount,
+            ),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                AccountType::NearImplicitAccount,
+            ),
+            (
+                "0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b",
+                AccountType::NearDeterministicAccount,
+            ),
+        ];
+        for (account_id, account_type) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(account_id.get_account_type(), account_type);
+            assert_eq!(account_id.is_implicit(), account_type.is_implicit());
+            assert_eq!(
+                account_id.is_named(),
+                account_type == AccountType::NamedAccount
+            );
+            assert_eq!(
+                account_id.is_near_implicit(),
+                account_type == AccountType::NearImplicitAccount
+            );
+            assert_eq!(
+                account_id.is_eth_implicit(),
+                account_type == AccountType::EthImplicitAccount
+            );
+            assert_eq!(
+                account_id.is_near_deterministic(),
+                account_type == AccountType::NearDeterministicAccount
             );
         }
+    }
 
-        let invalid_near_implicit_account_ids = &[
-            "000000000000000000000000000000000000000000000000000000000000000",
-            "6.74617461746174617461746174617461746174617461746174617461746174",
-            "012-456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
-            "fffff_ffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-            "oooooooooooooooooooooooooooooooooooooooooooooooooooooooooooooooo",
-            "00000000000000000000000000000000000000000000000000000000000000",
+    #[test]
+    fn test_near_implicit_public_key() {
+        for public_key in [[0; 32], [0xff; 32], std::array::from_fn(|i| (i * 7) as u8)] {
+            let account_id = AccountId::from_near_implicit(&public_key);
+            assert_eq!(account_id.near_implicit_public_key(), Some(public_key));
+        }
+
+        for account_id in [
+            "alice.near",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "012345678901234567890123456789012345678901234567890123456789012",
+        ] {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id).near_implicit_public_key(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_near_implicit_hex() {
+        let account_id = AccountId::from_near_implicit(&[0x42; 32]);
+        let hex = account_id.near_implicit_hex().unwrap();
+        assert_eq!(hex, "42".repeat(32));
+        assert_eq!(hex.as_ptr(), account_id.as_str().as_ptr());
+
+        for account_id in ["alice.near", "0xb794f5ea0ba39494ce839613fffba74279579268"] {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id).near_implicit_hex(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_eth_address() {
+        // xorshift64, good enough to get some variety in the addresses
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next_byte = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        };
+
+        for _ in 0..100 {
+            let address: [u8; 20] = std::array::from_fn(|_| next_byte());
+            let account_id = AccountId::from_eth_implicit(&address);
+            assert_eq!(AccountId::validate(account_id.as_str()), Ok(()));
+            assert!(account_id.get_account_type() == AccountType::EthImplicitAccount);
+            assert_eq!(account_id.eth_address(), Some(address));
+        }
+
+        for account_id in [
+            "alice.near",
+            "0x",
+            "0123456789012345678901234567890123456789012345678901234567890123",
+            "0x000000000000000000000000000000000000000",
+        ] {
+            assert_eq!(AccountIdRef::new_or_panic(account_id).eth_address(), None);
+        }
+    }
+
+    #[test]
+    fn test_eq_eth_address() {
+        let address = [0xab; 20];
+        let account_id = AccountId::from_eth_implicit(&address);
+        assert!(account_id.eq_eth_address(&address));
+
+        for i in 0..address.len() {
+            let mut near_miss = address;
+            near_miss[i] ^= 1;
+            assert!(!account_id.eq_eth_address(&near_miss));
+        }
+
+        // A NEAR-deterministic account with the same hex is not an ETH-implicit account.
+        let deterministic = format!("0s{}", "ab".repeat(20));
+        assert!(!AccountIdRef::new_or_panic(&deterministic).eq_eth_address(&address));
+        assert!(!AccountIdRef::new_or_panic("alice.near").eq_eth_address(&address));
+    }
+
+    #[test]
+    fn test_eq_ignore_ascii_case() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        for other in ["alice.near", "Alice.near", "ALICE.NEAR", "aLiCe.NeAr"] {
+            assert!(alice.eq_ignore_ascii_case(other), "{:?}", other);
+        }
+        for other in [
+            "bob.near",
+            "alice.near.",
+            "alice_near",
+            "alice",
+            "",
+            "ålice.near",
+        ] {
+            assert!(!alice.eq_ignore_ascii_case(other), "{:?}", other);
+        }
+
+        let eth = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+        assert!(eth.eq_ignore_ascii_case("0xB794F5EA0BA39494CE839613FFFBA74279579268"));
+    }
+
+    #[test]
+    fn test_eq_hex_ignore_case() {
+        let cases = [
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                "98793CD91A3F870FB126F66285808C7E094AFCFC4EDA8A970F6648CDF0DBD6DE",
+                true,
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                "0xB794F5eA0ba39494cE839613fffBA74279579268",
+                true,
+            ),
+            (
+                "0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b",
+                "0s7D8A1E5B4C3F2A1B0C9D8E7F6A5B4C3D2E1F0A9B",
+                true,
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                "0xB794F5eA0ba39494cE839613fffBA74279579269",
+                false,
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                "0xb794f5ea0ba39494ce839613fffba7427957926",
+                false,
+            ),
+            ("alice.near", "alice.near", false),
+            ("alice.near", "ALICE.NEAR", false),
         ];
-        for invalid_account_id in invalid_near_implicit_account_ids {
-            assert!(
-                !matches!(
-                    AccountIdRef::new(invalid_account_id),
-                    Ok(account_id) if account_id.get_account_type() == AccountType::NearImplicitAccount
-                ),
-                "Account ID {} is not a NEAR-implicit account",
-                invalid_account_id
+        for (account_id, other, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new_or_panic(account_id).eq_hex_ignore_case(other),
+                expected,
+                "{:?} vs {:?}",
+                account_id,
+                other
             );
         }
+
+        let (_, allocs) = crate::count_alloc::count_allocs(|| {
+            AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268")
+                .eq_hex_ignore_case("0xB794F5eA0ba39494cE839613fffBA74279579268")
+        });
+        assert_eq!(allocs, 0);
     }
 
     #[test]
-    fn test_is_account_id_eth_implicit() {
-        let valid_eth_implicit_account_ids = &[
-            "0x0000000000000000000000000000000000000000",
-            "0x6174617461746174617461746174617461746174",
-            "0x0123456789abcdef0123456789abcdef01234567",
-            "0xffffffffffffffffffffffffffffffffffffffff",
-            "0x20782e20662e64666420482123494b6b6c677573",
+    fn test_canonical_key() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert!(matches!(account_id.canonical_key(), Cow::Borrowed(key) if key == account_id));
+        }
+
+        let eth = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        assert!(AccountIdRef::new("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+        assert!(matches!(
+            AccountIdRef::new_or_panic(eth).canonical_key(),
+            Cow::Borrowed(key) if key == eth
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "internal_unstable")]
+    #[allow(deprecated)]
+    fn test_canonical_key_checksummed() {
+        let checksummed =
+            AccountId::new_unvalidated("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string());
+        let lowercase: AccountId = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            .parse()
+            .unwrap();
+
+        assert_eq!(checksummed.canonical_key(), lowercase.canonical_key());
+        assert!(matches!(checksummed.canonical_key(), Cow::Owned(_)));
+
+        // Only ETH-implicit accounts are canonicalized
+        let named = AccountId::new_unvalidated("Alice.near".to_string());
+        assert_eq!(named.canonical_key(), "Alice.near");
+    }
+
+    #[test]
+    fn test_is_account_id_near_deterministic() {
+        let valid_near_deterministic_account_ids = &[
+            "0s0000000000000000000000000000000000000000",
+            "0s6174617461746174617461746174617461746174",
+            "0s0123456789abcdef0123456789abcdef01234567",
+            "0sffffffffffffffffffffffffffffffffffffffff",
+            "0s20782e20662e64666420482123494b6b6c677573",
         ];
-        for valid_account_id in valid_eth_implicit_account_ids {
+        for valid_account_id in valid_near_deterministic_account_ids {
             assert!(
                 matches!(
                     valid_account_id.parse::<AccountId>(),
-                    Ok(account_id) if account_id.get_account_type() == AccountType::EthImplicitAccount
+                    Ok(account_id) if account_id.get_account_type() == AccountType::NearDeterministicAccount
                 ),
-                "Account ID {} should be valid 42-len hex, starting with 0x",
+                "Account ID {} should be valid 42-len hex, starting with 0s",
                 valid_account_id
             );
         }
 
-        let invalid_eth_implicit_account_ids = &[
+        let invalid_near_deterministic_account_ids = &[
             "04b794f5ea0ba39494ce839613fffba74279579268",
-            "0x000000000000000000000000000000000000000",
-            "0x6.74617461746174617461746174617461746174",
-            "0x012-456789abcdef0123456789abcdef01234567",
-            "0xfffff_ffffffffffffffffffffffffffffffffff",
-            "0xoooooooooooooooooooooooooooooooooooooooo",
-            "0x00000000000000000000000000000000000000000",
+            "0s000000000000000000000000000000000000000",
+            "0s6.74617461746174617461746174617461746174",
+            "0s012-456789abcdef0123456789abcdef01234567",
+            "0sfffff_ffffffffffffffffffffffffffffffffff",
+            "0soooooooooooooooooooooooooooooooooooooooo",
+            "0s00000000000000000000000000000000000000000",
+            "0x0000000000000000000000000000000000000000",
             "0000000000000000000000000000000000000000000000000000000000000000",
         ];
-        for invalid_account_id in invalid_eth_implicit_account_ids {
+        for invalid_account_id in invalid_near_deterministic_account_ids {
             assert!(
                 !matches!(
                     invalid_account_id.parse::<AccountId>(),
-                    Ok(account_id) if account_id.get_account_type() == AccountType::EthImplicitAccount
+                    Ok(account_id) if account_id.get_account_type() == AccountType::NearDeterministicAccount
                 ),
-                "Account ID {} is not an ETH-implicit account",
+                "Account ID {} is not a NEAR-deterministic account",
                 invalid_account_id
             );
         }
     }
 
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_shrinking() {
+        let input = "app.alice_1.near";
+
+        let mut previous: Option<&str> = None;
+        // Simulate a fuzzer minimizing its input by repeatedly dropping the last byte.
+        for len in (AccountIdRef::MIN_LEN..=input.len()).rev() {
+            let data = [&input.as_bytes()[..len], &[len as u8]].concat();
+            let mut u = arbitrary::Unstructured::new(&data);
+            let account_id = u.arbitrary::<&AccountIdRef>().unwrap().as_str();
+
+            assert!(input.starts_with(account_id));
+            if let Some(previous) = previous {
+                assert!(account_id.len() <= previous.len());
+                assert!(account_id.split('.').count() <= previous.split('.').count());
+            }
+            previous = Some(&input[..account_id.len()]);
+        }
+        assert_eq!(previous, Some("ap"));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_multibyte() {
+        let corpus = [
+            ("ab\u{20ac}cd", Some("ab")),
+            ("alice.\u{192}.near", Some("alice")),
+            ("\u{192}elicia.near", None),
+            ("a\u{1f600}bc", None),
+            ("alice_near\u{1f600}", Some("alice_near")),
+        ];
+
+        for (input, expected_output) in corpus {
+            let data = [input.as_bytes(), &[input.len() as _]].concat();
+            let mut u = arbitrary::Unstructured::new(&data);
+
+            assert_eq!(
+                u.arbitrary::<&AccountIdRef>()
+                    .ok()
+                    .map(AsRef::<str>::as_ref),
+                expected_output,
+                "{}",
+                input
+            );
+        }
+    }
+
     #[test]
     #[cfg(feature = "arbitrary")]
     fn test_arbitrary() {
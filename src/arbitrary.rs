@@ -0,0 +1,237 @@
+//! Adapters for generating specific kinds of Account IDs with [`arbitrary`].
+//!
+//! [`AccountId`] and [`&AccountIdRef`](AccountIdRef) implement [`Arbitrary`] by taking the
+//! longest valid prefix of an arbitrary string, which mostly yields named accounts, but may
+//! yield any account type. These adapters narrow that down, so that a fuzzer doesn't waste
+//! iterations on Account IDs it's not interested in:
+//!
+//! - [`NamedAccountId`] only generates named accounts, with at most `MAX_LABELS` labels.
+//! - [`ImplicitAccountId`] only generates NEAR-implicit, ETH-implicit and NEAR-deterministic
+//!   accounts, from random addresses.
+//! - [`WeightedAccountId`] generates a named account `NAMED` out of `NAMED + IMPLICIT` times,
+//!   and an implicit account otherwise.
+//!
+//! Each adapter derefs to the generated [`AccountId`], and its [`size_hint`](Arbitrary::size_hint)
+//! reflects the input it consumes.
+//!
+//! ## Examples
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use near_account_id::{arbitrary::NamedAccountId, AccountType};
+//!
+//! let mut u = Unstructured::new(b"app.alice.near\x0e");
+//! let account_id = NamedAccountId::<2>::arbitrary(&mut u).unwrap();
+//!
+//! assert_eq!(account_id.get_account_type(), AccountType::NamedAccount);
+//! assert_eq!(*account_id, "app.alice");
+//! ```
+
+use alloc::string::String;
+use core::ops::Deref;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{AccountId, AccountIdRef};
+
+/// Generates a named Account ID with at most `MAX_LABELS` labels.
+///
+/// Like [`AccountId`]'s own implementation, this keeps the generated Account ID a prefix
+/// of the input, so it shrinks along with the input.
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct NamedAccountId<const MAX_LABELS: usize = { usize::MAX }>(pub AccountId);
+
+/// Generates a NEAR-implicit, ETH-implicit or NEAR-deterministic Account ID.
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct ImplicitAccountId(pub AccountId);
+
+/// Generates a named Account ID with a `NAMED / (NAMED + IMPLICIT)` probability,
+/// and an implicit Account ID otherwise.
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct WeightedAccountId<const NAMED: u32, const IMPLICIT: u32>(pub AccountId);
+
+impl<'a, const MAX_LABELS: usize> Arbitrary<'a> for NamedAccountId<MAX_LABELS> {
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&AccountIdRef as Arbitrary>::size_hint(depth)
+    }
+
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut account_id = u.arbitrary::<&AccountIdRef>()?.as_str();
+
+        // Everything before a separator is a valid Account ID, as long as it's long enough.
+        if let Some((idx, _)) = account_id
+            .match_indices('.')
+            .nth(MAX_LABELS.wrapping_sub(1))
+        {
+            account_id = &account_id[..idx];
+        }
+        // Dropping the last character of an implicit Account ID leaves a named one.
+        if crate::is_near_implicit(account_id)
+            || crate::is_eth_implicit(account_id)
+            || crate::is_near_deterministic(account_id)
+        {
+            account_id = &account_id[..account_id.len() - 1];
+        }
+
+        AccountIdRef::new(account_id)
+            .map(|account_id| Self(account_id.into()))
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ImplicitAccountId {
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        // One byte for the account type, then a 20 or 32 byte address.
+        (1 + 20, Some(1 + 32))
+    }
+
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (prefix, len) = match u.int_in_range(0..=2u8)? {
+            0 => ("", 32),
+            1 => ("0x", 20),
+            _ => ("0s", 20),
+        };
+
+        let mut account_id = String::with_capacity(prefix.len() + len * 2);
+        account_id.push_str(prefix);
+        crate::hex::encode(u.bytes(len)?, &mut account_id);
+        Ok(Self(AccountId(account_id.into_boxed_str())))
+    }
+}
+
+impl<'a, const NAMED: u32, const IMPLICIT: u32> Arbitrary<'a>
+    for WeightedAccountId<NAMED, IMPLICIT>
+{
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (named_min, named_max) = NamedAccountId::<{ usize::MAX }>::size_hint(depth);
+        let (implicit_min, implicit_max) = ImplicitAccountId::size_hint(depth);
+        // Picking the account type takes up to 4 bytes, depending on the weights.
+        let max = named_max.zip(implicit_max).map(|(a, b)| 4 + a.max(b));
+        (named_min.min(implicit_min), max)
+    }
+
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let total = NAMED
+            .checked_add(IMPLICIT)
+            .filter(|&total| total > 0)
+            .ok_or(arbitrary::Error::IncorrectFormat)?;
+
+        if u.int_in_range(1..=total)? <= NAMED {
+            Ok(Self(u.arbitrary::<NamedAccountId>()?.0))
+        } else {
+            Ok(Self(u.arbitrary::<ImplicitAccountId>()?.0))
+        }
+    }
+}
+
+macro_rules! impl_adapter {
+    ($ty:ident $(<$(const $param:ident: $param_ty:ty),+>)?) => {
+        impl$(<$(const $param: $param_ty),+>)? Deref for $ty$(<$($param),+>)? {
+            type Target = AccountId;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl$(<$(const $param: $param_ty),+>)? From<$ty$(<$($param),+>)?> for AccountId {
+            fn from(adapter: $ty$(<$($param),+>)?) -> Self {
+                adapter.0
+            }
+        }
+    };
+}
+
+impl_adapter!(NamedAccountId<const MAX_LABELS: usize>);
+impl_adapter!(ImplicitAccountId);
+impl_adapter!(WeightedAccountId<const NAMED: u32, const IMPLICIT: u32>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::AccountType;
+
+    #[test]
+    fn test_named_account_id() {
+        let corpus = [
+            ("app.alice.near", 1, Some("app")),
+            ("app.alice.near", 2, Some("app.alice")),
+            ("app.alice.near", 3, Some("app.alice.near")),
+            ("a.alice.near", 1, None),
+            ("ab|cd", 3, Some("ab")),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                3,
+                Some("012345678901234567890123456789012345678901234567890123456789012"),
+            ),
+            (
+                "0x0123456789012345678901234567890123456789",
+                3,
+                Some("0x012345678901234567890123456789012345678"),
+            ),
+        ];
+
+        for (input, max_labels, expected_output) in corpus {
+            let data = [input.as_bytes(), &[input.len() as _]].concat();
+            let mut u = Unstructured::new(&data);
+
+            let account_id = match max_labels {
+                1 => u.arbitrary::<NamedAccountId<1>>().map(AccountId::from),
+                2 => u.arbitrary::<NamedAccountId<2>>().map(AccountId::from),
+                _ => u.arbitrary::<NamedAccountId>().map(AccountId::from),
+            };
+            assert_eq!(
+                account_id.ok().as_deref().map(AccountIdRef::as_str),
+                expected_output,
+                "{} with at most {} labels",
+                input,
+                max_labels
+            );
+        }
+    }
+
+    #[test]
+    fn test_implicit_account_id() {
+        let data: [u8; 64] = core::array::from_fn(|i| (i as u8).wrapping_mul(37));
+
+        let mut account_types = [false; 3];
+        for offset in 0..data.len() - ImplicitAccountId::size_hint(0).0 {
+            let mut u = Unstructured::new(&data[offset..]);
+            let Ok(account_id) = u.arbitrary::<ImplicitAccountId>() else {
+                continue;
+            };
+
+            assert!(AccountId::validate(account_id.as_str()).is_ok());
+            match account_id.get_account_type() {
+                AccountType::NearImplicitAccount => account_types[0] = true,
+                AccountType::EthImplicitAccount => account_types[1] = true,
+                AccountType::NearDeterministicAccount => account_types[2] = true,
+                AccountType::NamedAccount => panic!("{} is a named account", account_id.as_str()),
+            }
+        }
+        assert_eq!(account_types, [true; 3]);
+    }
+
+    #[test]
+    fn test_weighted_account_id() {
+        let named = Unstructured::new(b"alice.near\x0a")
+            .arbitrary::<WeightedAccountId<1, 0>>()
+            .unwrap();
+        assert_eq!(*named, "alice.near");
+
+        let implicit = Unstructured::new(&[0; 33])
+            .arbitrary::<WeightedAccountId<0, 1>>()
+            .unwrap();
+        assert_eq!(*implicit, "0".repeat(64));
+
+        let implicit = Unstructured::new(&[1; 33])
+            .arbitrary::<WeightedAccountId<1, 1>>()
+            .unwrap();
+        assert_eq!(implicit.get_account_type(), AccountType::EthImplicitAccount);
+
+        assert!(Unstructured::new(&[0; 33])
+            .arbitrary::<WeightedAccountId<0, 0>>()
+            .is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::{string::String, sync::Arc};
+use core::{borrow::Borrow, fmt, ops::Deref, str::FromStr};
+
+use crate::{AccountId, AccountIdRef, ParseAccountError};
+
+/// NEAR Account Identifier with shared ownership.
+///
+/// This is the same as [`AccountId`], except that it's backed by an [`Arc<str>`](Arc),
+/// so cloning it is a reference count bump instead of a heap allocation.
+/// Prefer it when the same Account ID is stored in many places.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{AccountId, ArcAccountId};
+///
+/// let alice: ArcAccountId = "alice.near".parse().unwrap();
+/// let also_alice = alice.clone();
+///
+/// assert_eq!(also_alice.get_parent_account_id().unwrap(), "near");
+/// assert_eq!(AccountId::from(also_alice), "alice.near");
+/// ```
+#[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
+pub struct ArcAccountId(Arc<str>);
+
+impl Deref for ArcAccountId {
+    type Target = AccountIdRef;
+
+    fn deref(&self) -> &Self::Target {
+        AccountIdRef::new_unvalidated(&*self.0)
+    }
+}
+
+impl AsRef<str> for ArcAccountId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<AccountIdRef> for ArcAccountId {
+    fn as_ref(&self) -> &AccountIdRef {
+        self
+    }
+}
+
+impl Borrow<AccountIdRef> for ArcAccountId {
+    fn borrow(&self) -> &AccountIdRef {
+        self
+    }
+}
+
+impl Borrow<str> for ArcAccountId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for ArcAccountId {
+    type Err = ParseAccountError;
+
+    fn from_str(account_id: &str) -> Result<Self, Self::Err> {
+        crate::validation::validate(account_id)?;
+        Ok(Self(account_id.into()))
+    }
+}
+
+impl TryFrom<String> for ArcAccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: String) -> Result<Self, Self::Error> {
+        crate::validation::validate(&account_id)?;
+        Ok(Self(account_id.into()))
+    }
+}
+
+impl fmt::Display for ArcAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<AccountId> for ArcAccountId {
+    fn from(account_id: AccountId) -> Self {
+        Self(account_id.0.into())
+    }
+}
+
+impl From<&AccountIdRef> for ArcAccountId {
+    fn from(account_id: &AccountIdRef) -> Self {
+        Self(account_id.as_str().into())
+    }
+}
+
+impl From<ArcAccountId> for AccountId {
+    fn from(account_id: ArcAccountId) -> Self {
+        Self(account_id.0.as_ref().into())
+    }
+}
+
+impl From<ArcAccountId> for Arc<str> {
+    fn from(account_id: ArcAccountId) -> Self {
+        account_id.0
+    }
+}
+
+impl PartialEq<AccountId> for ArcAccountId {
+    fn eq(&self, other: &AccountId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<ArcAccountId> for AccountId {
+    fn eq(&self, other: &ArcAccountId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<AccountIdRef> for ArcAccountId {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<ArcAccountId> for AccountIdRef {
+    fn eq(&self, other: &ArcAccountId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for ArcAccountId {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for ArcAccountId {
+    fn eq(&self, other: &&'a str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::count_alloc::count_allocs;
+
+    #[test]
+    fn test_clone_does_not_allocate() {
+        let alice: ArcAccountId = "alice.near".parse().unwrap();
+
+        let (clones, allocs) = count_allocs(|| [alice.clone(), alice.clone(), alice.clone()]);
+        assert_eq!(allocs, 0);
+        for clone in &clones {
+            assert_eq!(clone, &alice);
+            assert_eq!(clone.as_str().as_ptr(), alice.as_str().as_ptr());
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        let account_id: AccountId = "alice.near".parse().unwrap();
+
+        let arc = ArcAccountId::from(account_id.clone());
+        assert_eq!(arc, account_id);
+        assert_eq!(account_id, arc);
+        assert_eq!(arc, *AccountIdRef::new_or_panic("alice.near"));
+        assert_eq!(arc, "alice.near");
+        assert_eq!(arc.to_string(), "alice.near");
+        assert_eq!(arc.get_parent_account_id().unwrap(), "near");
+
+        assert_eq!(AccountId::from(arc.clone()), account_id);
+        assert_eq!(ArcAccountId::from(&*account_id), arc);
+        assert_eq!(
+            ArcAccountId::try_from("alice.near".to_string()).unwrap(),
+            arc
+        );
+
+        assert!("Alice.near".parse::<ArcAccountId>().is_err());
+        assert!(ArcAccountId::try_from("alice..near".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_hash_matches_account_id() {
+        use std::collections::HashSet;
+
+        let set: HashSet<ArcAccountId> = ["alice.near", "bob.near"]
+            .iter()
+            .map(|account_id| account_id.parse().unwrap())
+            .collect();
+
+        assert!(set.contains(AccountIdRef::new_or_panic("alice.near")));
+        assert!(set.contains("bob.near"));
+        assert!(!set.contains("carol.near"));
+    }
+}
@@ -1,33 +1,55 @@
-use crate::AccountIdRef;
+use crate::{AccountIdRef, ParseAccountError, ParseErrorKind};
 
 use super::AccountId;
 
-use std::io::{Read, Write};
+use alloc::{borrow::ToOwned, format, string::String};
 
+use borsh::io::{Read, Write};
 use borsh::{BorshDeserialize, BorshSerialize};
 
 impl BorshSerialize for AccountId {
-    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
         self.0.serialize(writer)
     }
 }
 
 impl BorshSerialize for AccountIdRef {
-    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
         self.0.serialize(writer)
     }
 }
 
 impl BorshDeserialize for AccountId {
-    fn deserialize_reader<R: Read>(rd: &mut R) -> std::io::Result<Self> {
-        let account_id = Box::<str>::deserialize_reader(rd)?;
-        crate::validation::validate(&account_id).map_err(|err| {
-            std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("invalid value: \"{}\", {}", account_id, err),
-            )
-        })?;
-        Ok(Self(account_id))
+    fn deserialize_reader<R: Read>(rd: &mut R) -> borsh::io::Result<Self> {
+        // Check the length prefix before reading any further, so that a hostile length
+        // fails immediately instead of reading (and allocating) up to 4GiB first.
+        let len = u32::deserialize_reader(rd)? as usize;
+        if len > AccountId::MAX_LEN {
+            let err = ParseAccountError {
+                kind: ParseErrorKind::TooLong,
+                char: None,
+            };
+            return Err(borsh::io::Error::new(
+                borsh::io::ErrorKind::InvalidData,
+                format!("invalid length: {}, {}", len, err),
+            ));
+        }
+
+        let mut buf = [0; AccountId::MAX_LEN];
+        let account_id = &mut buf[..len];
+        rd.read_exact(account_id)?;
+        AccountIdRef::from_bytes(account_id)
+            .map(ToOwned::to_owned)
+            .map_err(|err| {
+                borsh::io::Error::new(
+                    borsh::io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid value: \"{}\", {}",
+                        String::from_utf8_lossy(account_id),
+                        err
+                    ),
+                )
+            })
     }
 }
 
@@ -35,6 +57,7 @@ impl BorshDeserialize for AccountId {
 mod tests {
     use borsh::BorshDeserialize as _;
 
+    use crate::count_alloc::count_allocs;
     use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
     use crate::AccountId;
 
@@ -71,6 +94,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_error_message() {
+        let data = borsh::to_vec("alice..near").unwrap();
+        let err = AccountId::try_from_slice(&data).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "invalid value: \"alice..near\", the Account ID has a redundant separator '.' at index 6"
+        );
+
+        let data = borsh::to_vec(b"alice\xff.near".as_slice()).unwrap();
+        let err = AccountId::try_from_slice(&data).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: \"alice\u{fffd}.near\", the Account ID contains an invalid character '\u{fffd}' at index 5"
+        );
+    }
+
+    #[test]
+    fn test_oversized_length_prefix() {
+        for len in [AccountId::MAX_LEN as u32 + 1, 1 << 20, u32::MAX] {
+            let data = [&len.to_le_bytes()[..], b"alice.near"].concat();
+            let mut rd = data.as_slice();
+
+            let (err, allocs) =
+                count_allocs(|| AccountId::deserialize_reader(&mut rd).unwrap_err());
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            assert_eq!(
+                err.to_string(),
+                format!("invalid length: {}, the Account ID is too long", len)
+            );
+            // Only the error message is allocated, no matter the claimed length.
+            assert!(allocs < 10, "{} allocations", allocs);
+            // Nothing past the length prefix was consumed.
+            assert_eq!(rd, b"alice.near");
+        }
+
+        let data = [&64u32.to_le_bytes()[..], b"alice.near"].concat();
+        let err = AccountId::try_from_slice(&data).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
     #[test]
     fn fuzz() {
         bolero::check!().for_each(|input: &[u8]| {
@@ -0,0 +1,157 @@
+//! [clap](https://docs.rs/clap) support for Account IDs.
+//!
+//! [`AccountIdValueParser`] parses command line arguments into an [`AccountId`]. On invalid
+//! input, the error points at the offending part of the argument:
+//!
+//! ```text
+//! error: invalid value 'alice.Near' for '<ACCOUNT_ID>': the Account ID contains an invalid character 'N' at index 6
+//!
+//!   alice.Near
+//!         ^
+//! ```
+//!
+//! It's also used by `value_parser!(AccountId)`, so derived arguments of type [`AccountId`]
+//! pick it up without further configuration. It can be set explicitly too:
+//!
+//! ```ignore
+//! use clap::Parser;
+//! use near_account_id::AccountId;
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[arg(value_parser = AccountId::value_parser())]
+//!     account_id: AccountId,
+//! }
+//! ```
+
+use std::ffi::OsStr;
+
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error};
+
+use crate::AccountId;
+
+/// A [`TypedValueParser`] for [`AccountId`].
+///
+/// ## Examples
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use near_account_id::AccountId;
+///
+/// let cmd = Command::new("transfer")
+///     .arg(Arg::new("receiver").value_parser(AccountId::value_parser()));
+///
+/// let matches = cmd.clone().try_get_matches_from(["transfer", "alice.near"]).unwrap();
+/// assert_eq!(matches.get_one::<AccountId>("receiver").unwrap(), "alice.near");
+///
+/// assert!(cmd.try_get_matches_from(["transfer", "Alice.near"]).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AccountIdValueParser;
+
+impl AccountIdValueParser {
+    /// Creates the parser, see also [`AccountId::value_parser`].
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TypedValueParser for AccountIdValueParser {
+    type Value = AccountId;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        value.parse().map_err(|err: crate::ParseAccountError| {
+            let diagnostic = err.diagnostic(value);
+            let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+
+            let mut message = format!("invalid value '{}' for '{}': {}", value, arg, err);
+            if !diagnostic.span.is_empty() {
+                let offset = value[..diagnostic.span.start].chars().count();
+                let width = value[diagnostic.span].chars().count();
+                message += &format!(
+                    "\n\n  {}\n  {}{}",
+                    value,
+                    " ".repeat(offset),
+                    "^".repeat(width)
+                );
+            }
+            Error::raw(ErrorKind::ValueValidation, message).format(&mut cmd.clone())
+        })
+    }
+}
+
+impl ValueParserFactory for AccountId {
+    type Parser = AccountIdValueParser;
+
+    fn value_parser() -> Self::Parser {
+        AccountIdValueParser
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::value_parser;
+
+    use super::*;
+
+    use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
+
+    fn cmd() -> Command {
+        Command::new("transfer").arg(
+            Arg::new("receiver")
+                .required(true)
+                .value_parser(value_parser!(AccountId)),
+        )
+    }
+
+    #[test]
+    fn test_parse() {
+        for account_id in OK_ACCOUNT_IDS {
+            let matches = cmd()
+                .try_get_matches_from(["transfer", account_id])
+                .unwrap();
+            assert_eq!(
+                matches.get_one::<AccountId>("receiver").unwrap(),
+                account_id
+            );
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let err = cmd()
+                .try_get_matches_from(["transfer", "--", account_id])
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ValueValidation, "{:?}", account_id);
+        }
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = cmd()
+            .try_get_matches_from(["transfer", "alice.Near"])
+            .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "error: invalid value 'alice.Near' for '<receiver>': \
+             the Account ID contains an invalid character 'N' at index 6\n\
+             \n  alice.Near\
+             \n        ^\n"
+        ));
+
+        let err = cmd().try_get_matches_from(["transfer", "a"]).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "error: invalid value 'a' for '<receiver>': the Account ID is too short\n\
+             \n  a\
+             \n  ^\n"
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+//! Comparators for sorting Account IDs in ways other than their natural byte-lexicographic order.
+
+use core::cmp::Ordering;
+
+use crate::AccountIdRef;
+
+/// Orders Account IDs by their [`AccountType`](crate::AccountType) first (named accounts,
+/// then the implicit kinds), and lexicographically within each type.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{cmp, AccountId};
+///
+/// let mut accounts: Vec<AccountId> = vec![
+///     "0xb794f5ea0ba39494ce839613fffba74279579268".parse().unwrap(),
+///     "bob.near".parse().unwrap(),
+///     "alice.near".parse().unwrap(),
+/// ];
+/// accounts.sort_by(|a, b| cmp::by_type_then_name(a, b));
+///
+/// assert_eq!(accounts[0], "alice.near");
+/// assert_eq!(accounts[1], "bob.near");
+/// assert_eq!(accounts[2], "0xb794f5ea0ba39494ce839613fffba74279579268");
+/// ```
+pub fn by_type_then_name(a: &AccountIdRef, b: &AccountIdRef) -> Ordering {
+    a.get_account_type()
+        .cmp(&b.get_account_type())
+        .then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccountId;
+
+    #[test]
+    fn test_by_type_then_name() {
+        let mut accounts: Vec<AccountId> = [
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "zoe.near",
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+            "0x0000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "alice.near",
+            "0x",
+            "near",
+        ]
+        .iter()
+        .map(|account_id| account_id.parse().unwrap())
+        .collect();
+
+        accounts.sort_by(|a, b| by_type_then_name(a, b));
+
+        assert_eq!(
+            accounts,
+            [
+                "0x",
+                "alice.near",
+                "near",
+                "zoe.near",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                "0x0000000000000000000000000000000000000000",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+};
+use core::fmt;
+
+use crate::{AccountId, AccountIdRef, AccountType};
+
+/// NEAR Account Identifier that stores implicit accounts as raw bytes.
+///
+/// Implicit accounts are hex encodings of a public key or an address, so their string form
+/// takes twice the space of the bytes they encode, plus a heap allocation. This keeps named
+/// accounts as an [`AccountId`], but implicit accounts inline as their decoded bytes, and only
+/// rebuilds their string form on demand with [`CompactAccountId::as_account_id`].
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{AccountId, CompactAccountId};
+///
+/// let implicit: AccountId = "ab".repeat(32).parse().unwrap();
+/// let compact = CompactAccountId::from(implicit.clone());
+///
+/// assert_eq!(compact, CompactAccountId::NearImplicit([0xab; 32]));
+/// assert_eq!(*compact.as_account_id(), implicit);
+/// assert_eq!(AccountId::from(compact), implicit);
+/// ```
+#[derive(Eq, Hash, Clone, PartialEq)]
+pub enum CompactAccountId {
+    /// A named account, see [`AccountType::NamedAccount`].
+    Named(AccountId),
+    /// The public key of a NEAR-implicit account, see [`AccountType::NearImplicitAccount`].
+    NearImplicit([u8; 32]),
+    /// The address of an ETH-implicit account, see [`AccountType::EthImplicitAccount`].
+    EthImplicit([u8; 20]),
+    /// The address of a NEAR-deterministic account, see [`AccountType::NearDeterministicAccount`].
+    NearDeterministic([u8; 20]),
+}
+
+impl CompactAccountId {
+    /// Returns the Account ID, borrowing named accounts and rebuilding implicit ones.
+    pub fn as_account_id(&self) -> Cow<'_, AccountIdRef> {
+        match self {
+            CompactAccountId::Named(account_id) => Cow::Borrowed(account_id),
+            CompactAccountId::NearImplicit(public_key) => {
+                Cow::Owned(AccountId::from_near_implicit(public_key))
+            }
+            CompactAccountId::EthImplicit(address) => {
+                Cow::Owned(AccountId::from_eth_implicit(address))
+            }
+            CompactAccountId::NearDeterministic(address) => {
+                let mut account_id = String::with_capacity(42);
+                account_id.push_str("0s");
+                crate::hex::encode(address, &mut account_id);
+                Cow::Owned(AccountId(account_id.into_boxed_str()))
+            }
+        }
+    }
+
+    /// Returns the type of the Account ID, without rebuilding it.
+    pub fn get_account_type(&self) -> AccountType {
+        match self {
+            CompactAccountId::Named(_) => AccountType::NamedAccount,
+            CompactAccountId::NearImplicit(_) => AccountType::NearImplicitAccount,
+            CompactAccountId::EthImplicit(_) => AccountType::EthImplicitAccount,
+            CompactAccountId::NearDeterministic(_) => AccountType::NearDeterministicAccount,
+        }
+    }
+
+    /// Decodes an implicit Account ID, or returns `None` for named accounts.
+    fn from_implicit(account_id: &AccountIdRef) -> Option<Self> {
+        match account_id.get_account_type() {
+            AccountType::NamedAccount => None,
+            AccountType::NearImplicitAccount => {
+                crate::hex::decode(account_id.as_bytes()).map(CompactAccountId::NearImplicit)
+            }
+            AccountType::EthImplicitAccount => {
+                crate::hex::decode(&account_id.as_bytes()[2..]).map(CompactAccountId::EthImplicit)
+            }
+            AccountType::NearDeterministicAccount => {
+                crate::hex::decode(&account_id.as_bytes()[2..])
+                    .map(CompactAccountId::NearDeterministic)
+            }
+        }
+    }
+}
+
+impl From<AccountId> for CompactAccountId {
+    fn from(account_id: AccountId) -> Self {
+        Self::from_implicit(&account_id).unwrap_or(CompactAccountId::Named(account_id))
+    }
+}
+
+impl From<&AccountIdRef> for CompactAccountId {
+    fn from(account_id: &AccountIdRef) -> Self {
+        Self::from_implicit(account_id)
+            .unwrap_or_else(|| CompactAccountId::Named(account_id.to_owned()))
+    }
+}
+
+impl From<CompactAccountId> for AccountId {
+    fn from(account_id: CompactAccountId) -> Self {
+        match account_id {
+            CompactAccountId::Named(account_id) => account_id,
+            implicit => implicit.as_account_id().into_owned(),
+        }
+    }
+}
+
+impl fmt::Debug for CompactAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_account_id().as_str(), f)
+    }
+}
+
+impl fmt::Display for CompactAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_account_id().as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::count_alloc::count_allocs;
+    use crate::test_data::OK_ACCOUNT_IDS;
+
+    #[test]
+    fn test_round_trip() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+
+            let compact = CompactAccountId::from(account_id.clone());
+            assert_eq!(compact.get_account_type(), account_id.get_account_type());
+            assert_eq!(compact, CompactAccountId::from(&*account_id));
+            assert_eq!(*compact.as_account_id(), account_id);
+            assert_eq!(compact.to_string(), account_id.as_str());
+            assert_eq!(AccountId::from(compact), account_id);
+        }
+    }
+
+    #[test]
+    fn test_compact() {
+        assert!(core::mem::size_of::<CompactAccountId>() <= 40);
+
+        let cases = [
+            (
+                "0x".to_string() + &"ab".repeat(20),
+                CompactAccountId::EthImplicit([0xab; 20]),
+            ),
+            (
+                "0s".to_string() + &"cd".repeat(20),
+                CompactAccountId::NearDeterministic([0xcd; 20]),
+            ),
+            ("ef".repeat(32), CompactAccountId::NearImplicit([0xef; 32])),
+        ];
+        for (account_id, expected) in cases {
+            let account_id: AccountId = account_id.parse().unwrap();
+            let (compact, allocs) = count_allocs(|| CompactAccountId::from(&*account_id));
+            assert_eq!(compact, expected);
+            assert_eq!(allocs, 0);
+        }
+
+        // Named accounts keep their allocation.
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let ptr = alice.as_bytes().as_ptr();
+        let (compact, allocs) = count_allocs(|| CompactAccountId::from(alice));
+        assert_eq!(allocs, 0);
+        let (alice, allocs) = count_allocs(|| AccountId::from(compact));
+        assert_eq!(allocs, 0);
+        assert_eq!(alice.as_bytes().as_ptr(), ptr);
+    }
+}
@@ -1,5 +1,7 @@
-use std::fmt;
-use std::fmt::Write;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::fmt::Write;
+use core::ops::Range;
 
 /// An error which can be returned when parsing a NEAR Account ID.
 #[derive(Eq, Clone, Debug, PartialEq)]
@@ -13,8 +15,58 @@ impl ParseAccountError {
     pub fn kind(&self) -> &ParseErrorKind {
         &self.kind
     }
+
+    /// Returns a machine-readable [`Diagnostic`] for this error, e.g. for editor tooling.
+    ///
+    /// `account_id` must be the input that failed to parse, since the error doesn't keep it.
+    /// The span covers the offending character for character errors, and the whole input
+    /// otherwise.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let input = "ƒelicia.near";
+    /// let diagnostic = AccountId::validate(input).unwrap_err().diagnostic(input);
+    ///
+    /// assert_eq!(diagnostic.kind, ParseErrorKind::InvalidChar);
+    /// assert_eq!(&input[diagnostic.span], "ƒ");
+    /// assert_eq!(diagnostic.message, "the Account ID contains an invalid character 'ƒ' at index 0");
+    /// ```
+    pub fn diagnostic(&self, account_id: &str) -> Diagnostic {
+        // Errors from `validate_all` report char offsets, which only match byte offsets when
+        // everything before them is ASCII, so look the byte offset up by char instead.
+        let span = self
+            .char
+            .and_then(|(idx, char)| {
+                let (start, found) = account_id.char_indices().nth(idx)?;
+                (found == char).then(|| start..start + char.len_utf8())
+            })
+            .unwrap_or(0..account_id.len());
+
+        Diagnostic {
+            kind: self.kind.clone(),
+            span,
+            message: self.to_string(),
+        }
+    }
 }
 
+/// A machine-readable description of a [`ParseAccountError`].
+///
+/// Returned by [`ParseAccountError::diagnostic`].
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The specific cause why parsing the Account ID failed.
+    pub kind: ParseErrorKind,
+    /// The byte range of the input the error applies to.
+    pub span: Range<usize>,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+#[cfg(feature = "std")]
 impl std::error::Error for ParseAccountError {}
 impl fmt::Display for ParseAccountError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -53,6 +105,61 @@ pub enum ParseErrorKind {
     ///
     /// Cases: `ƒelicia.near`, `user@app.com`, `Emily.near`.
     InvalidChar,
+    /// The Account ID is well-formed, but reserved by the protocol.
+    ///
+    /// This variant is only returned by [`AccountId::validate_strict`](crate::AccountId::validate_strict),
+    /// the default validation accepts reserved Account IDs.
+    ///
+    /// Cases: `system`
+    Reserved,
+}
+
+impl ParseErrorKind {
+    /// Returns a stable, machine-readable name of the error kind, e.g. for logging.
+    ///
+    /// Unlike the [`Display`](fmt::Display) implementation, which describes the error for
+    /// humans, these names will not change across crate versions. `ParseErrorKind` is
+    /// `#[non_exhaustive]` though, so new kinds may add new names, and code mapping the names
+    /// back needs a fallback.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// assert_eq!(ParseErrorKind::InvalidChar.as_str(), "invalid-char");
+    ///
+    /// let err = AccountId::validate("alice..near").unwrap_err();
+    /// assert_eq!(err.kind().as_str(), "redundant-separator");
+    /// ```
+    ///
+    /// Matching on the kind instead of its name needs a wildcard arm for the same reason:
+    ///
+    /// ```
+    /// use near_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// fn exit_code(kind: &ParseErrorKind) -> i32 {
+    ///     match kind {
+    ///         ParseErrorKind::TooLong | ParseErrorKind::TooShort => 2,
+    ///         ParseErrorKind::RedundantSeparator | ParseErrorKind::InvalidChar => 3,
+    ///         // `Reserved`, and any kind added later.
+    ///         _ => 1,
+    ///     }
+    /// }
+    ///
+    /// let err = AccountId::validate_strict("system").unwrap_err();
+    /// assert_eq!(err.kind().as_str(), "reserved");
+    /// assert_eq!(exit_code(err.kind()), 1);
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ParseErrorKind::TooLong => "too-long",
+            ParseErrorKind::TooShort => "too-short",
+            ParseErrorKind::RedundantSeparator => "redundant-separator",
+            ParseErrorKind::InvalidChar => "invalid-char",
+            ParseErrorKind::Reserved => "reserved",
+        }
+    }
 }
 
 impl fmt::Display for ParseErrorKind {
@@ -62,6 +169,129 @@ impl fmt::Display for ParseErrorKind {
             ParseErrorKind::TooShort => "the Account ID is too short".fmt(f),
             ParseErrorKind::RedundantSeparator => "the Account ID has a redundant separator".fmt(f),
             ParseErrorKind::InvalidChar => "the Account ID contains an invalid character".fmt(f),
+            ParseErrorKind::Reserved => "the Account ID is reserved".fmt(f),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`AccountType`](crate::AccountType)
+/// from its canonical name.
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct ParseAccountTypeError(pub(crate) ());
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseAccountTypeError {}
+impl fmt::Display for ParseAccountTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "unknown account type".fmt(f)
+    }
+}
+
+/// An error which is returned when the labels of an Account ID don't fit into a buffer.
+///
+/// This is returned by [`AccountIdRef::labels_into`](crate::AccountIdRef::labels_into).
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct TooManyLabels(pub(crate) ());
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooManyLabels {}
+impl fmt::Display for TooManyLabels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "the Account ID has too many labels".fmt(f)
+    }
+}
+
+/// An error which is returned when an Account ID is expected to be a top-level account, but isn't.
+///
+/// This is returned by [`AccountId::rebase_tla`](crate::AccountId::rebase_tla).
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct NotTopLevelAccount(pub(crate) ());
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotTopLevelAccount {}
+impl fmt::Display for NotTopLevelAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "the Account ID is not a top-level account".fmt(f)
+    }
+}
+
+/// A reason why a syntactically valid Account ID cannot be registered.
+///
+/// This is returned by [`AccountIdRef::registration_error`](crate::AccountIdRef::registration_error),
+/// and is distinct from [`ParseAccountError`], which only concerns the Account ID grammar.
+#[non_exhaustive]
+#[derive(Eq, Clone, Copy, Debug, PartialEq)]
+pub enum RegistrationError {
+    /// The Account ID is reserved by the protocol.
+    ///
+    /// Cases: `system`
+    Reserved,
+    /// The Account ID is an implicit account.
+    ///
+    /// Implicit accounts are not registered, they are created by transferring tokens to them.
+    Implicit,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RegistrationError {}
+impl fmt::Display for RegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistrationError::Reserved => "the Account ID is reserved".fmt(f),
+            RegistrationError::Implicit => "the Account ID is an implicit account".fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{validation, AccountId, ParseErrorKind};
+
+    #[test]
+    fn test_diagnostic() {
+        let corpus = [
+            ("a", ParseErrorKind::TooShort, "a"),
+            ("alice.near.", ParseErrorKind::RedundantSeparator, "."),
+            ("alice..near", ParseErrorKind::RedundantSeparator, "."),
+            ("aliCe.near", ParseErrorKind::InvalidChar, "C"),
+            ("alice.nƒar", ParseErrorKind::InvalidChar, "ƒ"),
+            ("system", ParseErrorKind::Reserved, "system"),
+        ];
+        for (input, kind, highlighted) in corpus {
+            let err = AccountId::validate_strict(input).unwrap_err();
+            let diagnostic = err.diagnostic(input);
+            assert_eq!(diagnostic.kind, kind, "{}", input);
+            assert_eq!(&input[diagnostic.span], highlighted, "{}", input);
+            assert_eq!(diagnostic.message, err.to_string());
+        }
+
+        let input = "a".repeat(65);
+        let diagnostic = AccountId::validate(&input).unwrap_err().diagnostic(&input);
+        assert_eq!(diagnostic.kind, ParseErrorKind::TooLong);
+        assert_eq!(diagnostic.span, 0..65);
+
+        // `validate_all` keeps going after non-ASCII chars, so its offsets are char offsets.
+        let input = "ƒƒ..near";
+        let spans: Vec<_> = validation::validate_all(input)
+            .unwrap_err()
+            .iter()
+            .map(|err| err.diagnostic(input).span)
+            .collect();
+        assert_eq!(spans, [0..2, 2..4, 5..6]);
+    }
+
+    #[test]
+    fn test_kind_as_str() {
+        let cases = [
+            (ParseErrorKind::TooLong, "too-long"),
+            (ParseErrorKind::TooShort, "too-short"),
+            (ParseErrorKind::RedundantSeparator, "redundant-separator"),
+            (ParseErrorKind::InvalidChar, "invalid-char"),
+            (ParseErrorKind::Reserved, "reserved"),
+        ];
+        for (kind, expected) in cases {
+            assert_eq!(kind.as_str(), expected);
+            assert_ne!(kind.to_string(), expected);
         }
     }
 }
@@ -0,0 +1,55 @@
+//! Minimal lowercase hex helpers for implicit account addresses.
+
+use alloc::string::String;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Appends the lowercase hex encoding of `bytes` to `out`.
+pub fn encode(bytes: &[u8], out: &mut String) {
+    for byte in bytes {
+        out.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        out.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+}
+
+/// Decodes exactly `N` bytes from lowercase hex digits.
+pub fn decode<const N: usize>(hex: &[u8]) -> Option<[u8; N]> {
+    if hex.len() != N * 2 {
+        return None;
+    }
+
+    let mut bytes = [0; N];
+    for (byte, digits) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (decode_digit(digits[0])? << 4) | decode_digit(digits[1])?;
+    }
+    Some(bytes)
+}
+
+fn decode_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let mut out = String::new();
+        encode(&[0x00, 0x01, 0xab, 0xff], &mut out);
+        assert_eq!(out, "0001abff");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(b"0001abff"), Some([0x00, 0x01, 0xab, 0xff]));
+        assert_eq!(decode::<4>(b"0001abf"), None);
+        assert_eq!(decode::<4>(b"0001abfff"), None);
+        assert_eq!(decode::<4>(b"0001ABFF"), None);
+        assert_eq!(decode::<4>(b"0001abfg"), None);
+    }
+}
@@ -18,6 +18,8 @@
 //!   - `alice..near` ✗
 //!   - `not-_alice.near` ✗
 //! - An **Account ID** that is 64 characters long and consists of lowercase hex characters is a specific **implicit account ID**
+//! - An **Account ID** that is `0x` or `0s` followed by 40 lowercase hex characters is a specific
+//!   **ETH-implicit** or **NEAR-deterministic account ID**, respectively
 //!
 //! Learn more here: <https://docs.near.org/docs/concepts/account#account-id-rules>
 //!
@@ -34,19 +36,69 @@
 //!
 //! assert!("ƒelicia.near".parse::<AccountId>().is_err()); // (ƒ is not f)
 //! ```
+//!
+//! ## `no_std`
+//!
+//! This crate only needs `alloc` when the default `std` feature is disabled. Without `std`,
+//! the error types don't implement `std::error::Error`, and `validate_lines` is unavailable.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod macros;
 
 mod errors;
 
 mod account_id;
+mod account_id_builder;
 mod account_id_ref;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+mod arc_account_id;
 #[cfg(feature = "borsh")]
 mod borsh;
+#[cfg(feature = "clap")]
+pub mod clap;
+pub mod cmp;
+#[cfg(feature = "compact")]
+mod compact_account_id;
+#[cfg(test)]
+mod count_alloc;
+mod hex;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
 #[cfg(feature = "serde")]
-mod serde;
+pub mod serde;
+#[cfg(feature = "sqlx")]
+mod sqlx;
 #[cfg(test)]
 mod test_data;
+mod try_into_account_id;
 mod validation;
 
 pub use account_id::AccountId;
-pub use account_id_ref::{AccountIdRef, AccountType};
-pub use errors::{ParseAccountError, ParseErrorKind};
+pub use account_id_builder::AccountIdBuilder;
+pub use account_id_ref::{AccountIdRef, AccountType, Token};
+pub use arc_account_id::ArcAccountId;
+#[cfg(feature = "compact")]
+pub use compact_account_id::CompactAccountId;
+pub use errors::{
+    Diagnostic, NotTopLevelAccount, ParseAccountError, ParseAccountTypeError, ParseErrorKind,
+    RegistrationError, TooManyLabels,
+};
+pub use try_into_account_id::TryIntoAccountId;
+#[cfg(feature = "std")]
+pub use validation::validate_lines;
+pub use validation::{
+    is_eth_implicit, is_near_deterministic, is_near_implicit, Validator, ACCOUNT_ID_CHARSET,
+    ACCOUNT_ID_PATTERN, SEPARATORS,
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{format, string::String};
+}
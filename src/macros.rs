@@ -0,0 +1,97 @@
+/// Creates a [`&'static AccountIdRef`](crate::AccountIdRef) from a string literal, validated at compile time.
+///
+/// This is a shorthand for [`AccountIdRef::new_or_panic`](crate::AccountIdRef::new_or_panic),
+/// forcing the validation to happen at compile time even outside of const contexts.
+/// Use [`ToOwned::to_owned`] to get an owned [`AccountId`](crate::AccountId).
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{account_id, AccountId, AccountIdRef};
+///
+/// let alice: &AccountIdRef = account_id!("alice.near");
+/// let bob: AccountId = account_id!("bob.near").to_owned();
+///
+/// assert_eq!(alice, "alice.near");
+/// assert_eq!(bob, "bob.near");
+/// ```
+///
+/// Invalid literals fail to compile:
+///
+/// ```compile_fail
+/// let invalid = near_account_id::account_id!("alice..near");
+/// ```
+#[macro_export]
+macro_rules! account_id {
+    ($account_id:literal) => {{
+        const ACCOUNT_ID: &$crate::AccountIdRef = $crate::AccountIdRef::new_or_panic($account_id);
+        ACCOUNT_ID
+    }};
+}
+
+/// Formats the arguments like [`format!`] and validates the result as an [`AccountId`](crate::AccountId).
+///
+/// Evaluates to a `Result<AccountId, ParseAccountError>`.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{try_account_id, AccountId, ParseErrorKind};
+///
+/// let parent: AccountId = "alice.near".parse().unwrap();
+///
+/// let app = try_account_id!("{}.{}", "app", parent).unwrap();
+/// assert_eq!(app, "app.alice.near");
+///
+/// let err = try_account_id!("{}.{}", "App", parent).unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+/// ```
+#[macro_export]
+macro_rules! try_account_id {
+    ($($arg:tt)*) => {
+        <$crate::AccountId as ::core::convert::TryFrom<$crate::__private::String>>::try_from(
+            $crate::__private::format!($($arg)*),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccountIdRef, ParseAccountError, ParseErrorKind};
+
+    #[test]
+    fn test_account_id_macro() {
+        const ALICE: &AccountIdRef = account_id!("alice.near");
+        assert_eq!(ALICE, AccountIdRef::new("alice.near").unwrap());
+
+        let implicit =
+            account_id!("0123456789012345678901234567890123456789012345678901234567890123");
+        assert!(implicit.get_account_type().is_implicit());
+    }
+
+    #[test]
+    fn test_try_account_id_macro() {
+        let leaf = "app";
+        let parent = AccountIdRef::new_or_panic("alice.near");
+
+        assert_eq!(
+            try_account_id!("{}.{}", leaf, parent).unwrap(),
+            "app.alice.near"
+        );
+        assert_eq!(try_account_id!("{leaf}.near").unwrap(), "app.near");
+        assert_eq!(
+            try_account_id!("{}.{}", "App", parent),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::InvalidChar,
+                char: Some((0, 'A')),
+            })
+        );
+        assert_eq!(
+            try_account_id!("{}.{}", "", parent),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: Some((0, '.')),
+            })
+        );
+    }
+}
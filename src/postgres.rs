@@ -0,0 +1,133 @@
+//! [postgres-types](https://docs.rs/postgres-types) support for Account IDs.
+//!
+//! [`AccountId`] and [`&AccountIdRef`](AccountIdRef) are stored as text, so they can be used
+//! with `TEXT`, `VARCHAR` and similar columns. Reading a column validates the Account ID, and
+//! an invalid stored value is reported as an error.
+//!
+//! ## Examples
+//!
+//! ```
+//! use near_account_id::AccountId;
+//! use postgres_types::{FromSql, Type};
+//!
+//! let alice = AccountId::from_sql(&Type::TEXT, b"alice.near").unwrap();
+//! assert_eq!(alice, "alice.near");
+//!
+//! let err = AccountId::from_sql(&Type::TEXT, b"Alice.near").unwrap_err();
+//! assert_eq!(err.to_string(), "the Account ID contains an invalid character 'A' at index 0");
+//! ```
+
+use std::error::Error;
+
+use bytes::BytesMut;
+use postgres_types::{FromSql, IsNull, ToSql, Type};
+
+use crate::{AccountId, AccountIdRef};
+
+impl ToSql for &AccountIdRef {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    postgres_types::to_sql_checked!();
+}
+
+impl ToSql for AccountId {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    postgres_types::to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for &'a AccountIdRef {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let account_id = <&str as FromSql>::from_sql(ty, raw)?;
+        Ok(AccountIdRef::new(account_id)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a> FromSql<'a> for AccountId {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        <&AccountIdRef as FromSql>::from_sql(ty, raw).map(ToOwned::to_owned)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&AccountIdRef as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
+    use crate::ParseAccountError;
+
+    #[test]
+    fn test_round_trip() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+
+            let mut buf = BytesMut::new();
+            let is_null = account_id.to_sql_checked(&Type::TEXT, &mut buf).unwrap();
+            assert!(matches!(is_null, IsNull::No));
+            assert_eq!(&buf[..], account_id.as_bytes());
+
+            assert_eq!(AccountId::from_sql(&Type::TEXT, &buf).unwrap(), account_id);
+            let borrowed = <&AccountIdRef>::from_sql(&Type::VARCHAR, &buf).unwrap();
+            assert_eq!(borrowed.as_bytes().as_ptr(), buf.as_ptr());
+
+            let mut borrowed_buf = BytesMut::new();
+            borrowed
+                .to_sql_checked(&Type::VARCHAR, &mut borrowed_buf)
+                .unwrap();
+            assert_eq!(borrowed_buf, buf);
+        }
+    }
+
+    #[test]
+    fn test_invalid_stored_value() {
+        for account_id in BAD_ACCOUNT_IDS {
+            let err = AccountId::from_sql(&Type::TEXT, account_id.as_bytes()).unwrap_err();
+            assert!(err.is::<ParseAccountError>(), "{:?}: {}", account_id, err);
+        }
+
+        assert!(AccountId::from_sql(&Type::TEXT, b"alice\xff.near").is_err());
+    }
+
+    #[test]
+    fn test_accepts() {
+        for ty in [Type::TEXT, Type::VARCHAR, Type::BPCHAR, Type::NAME] {
+            assert!(<AccountId as ToSql>::accepts(&ty), "{}", ty);
+            assert!(<AccountId as FromSql>::accepts(&ty), "{}", ty);
+        }
+        assert!(!<AccountId as ToSql>::accepts(&Type::INT4));
+        assert!(!<AccountId as FromSql>::accepts(&Type::BYTEA));
+
+        let alice: AccountId = "alice.near".parse().unwrap();
+        assert!(alice
+            .to_sql_checked(&Type::INT4, &mut BytesMut::new())
+            .is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! [rkyv](https://docs.rs/rkyv) support for Account IDs.
+//!
+//! [`AccountId`] is archived as an [`ArchivedAccountId`], which stores the Account ID bytes
+//! inline in the archive. With `rkyv`'s validation (e.g. [`rkyv::check_archived_root`]),
+//! the archived Account ID is validated on access, so a corrupted archive is rejected
+//! instead of handing out an invalid Account ID.
+//!
+//! ## Examples
+//!
+//! ```
+//! use near_account_id::AccountId;
+//!
+//! let alice: AccountId = "alice.near".parse().unwrap();
+//! let bytes = rkyv::to_bytes::<_, 64>(&alice).unwrap();
+//!
+//! let archived = rkyv::check_archived_root::<AccountId>(&bytes).unwrap();
+//! assert_eq!(archived.as_str(), "alice.near");
+//! ```
+
+use alloc::borrow::ToOwned;
+use core::{fmt, ops::Deref};
+
+use rkyv::{
+    string::{ArchivedString, StringResolver},
+    Archive, Deserialize, Fallible, Serialize, SerializeUnsized,
+};
+
+use crate::{AccountId, AccountIdRef, ParseAccountError};
+
+/// An archived [`AccountId`].
+///
+/// This borrows directly from the archive, so it can be used without deserializing
+/// (e.g. from a memory-mapped file).
+#[derive(Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct ArchivedAccountId(ArchivedString);
+
+impl ArchivedAccountId {
+    /// Returns a string slice of the archived Account ID, borrowed from the archive.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Deref for ArchivedAccountId {
+    type Target = AccountIdRef;
+
+    fn deref(&self) -> &Self::Target {
+        AccountIdRef::new_unvalidated(self.as_str())
+    }
+}
+
+impl AsRef<AccountIdRef> for ArchivedAccountId {
+    fn as_ref(&self) -> &AccountIdRef {
+        self
+    }
+}
+
+impl fmt::Debug for ArchivedAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArchivedAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq<str> for ArchivedAccountId {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<AccountId> for ArchivedAccountId {
+    fn eq(&self, other: &AccountId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Archive for AccountId {
+    type Archived = ArchivedAccountId;
+    type Resolver = StringResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        // `ArchivedAccountId` is a transparent wrapper around `ArchivedString`.
+        ArchivedString::resolve_from_str(self.as_str(), pos, resolver, out.cast());
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for AccountId
+where
+    str: SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<AccountId, D> for ArchivedAccountId {
+    fn deserialize(&self, _: &mut D) -> Result<AccountId, D::Error> {
+        Ok((**self).to_owned())
+    }
+}
+
+/// An error returned when checking an [`ArchivedAccountId`] fails.
+#[derive(Debug)]
+pub enum CheckAccountIdError<E> {
+    /// The archived bytes are not a valid string.
+    String(E),
+    /// The archived string is not a valid Account ID.
+    AccountId(ParseAccountError),
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for CheckAccountIdError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckAccountIdError::String(err) => Some(err),
+            CheckAccountIdError::AccountId(err) => Some(err),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CheckAccountIdError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckAccountIdError::String(err) => write!(f, "invalid archived string: {}", err),
+            CheckAccountIdError::AccountId(err) => write!(f, "invalid Account ID: {}", err),
+        }
+    }
+}
+
+const _: () = {
+    use rkyv::{bytecheck::CheckBytes, validation::ArchiveContext};
+
+    impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedAccountId
+    where
+        ArchivedString: CheckBytes<C>,
+    {
+        type Error = CheckAccountIdError<<ArchivedString as CheckBytes<C>>::Error>;
+
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            let string = ArchivedString::check_bytes(value.cast(), context)
+                .map_err(CheckAccountIdError::String)?;
+            crate::validation::validate(string.as_str()).map_err(CheckAccountIdError::AccountId)?;
+            Ok(&*value)
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use rkyv::Deserialize as _;
+
+    use super::*;
+
+    use crate::test_data::OK_ACCOUNT_IDS;
+
+    #[test]
+    fn test_round_trip() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+            let bytes = rkyv::to_bytes::<_, 64>(&account_id).unwrap();
+
+            let archived = rkyv::check_archived_root::<AccountId>(&bytes).unwrap();
+            assert_eq!(archived.as_str(), account_id.as_str());
+            assert_eq!(*archived, account_id);
+            assert!(bytes.as_ptr_range().contains(&archived.as_str().as_ptr()));
+
+            let deserialized: AccountId = archived.deserialize(&mut rkyv::Infallible).unwrap();
+            assert_eq!(deserialized, account_id);
+        }
+    }
+
+    #[test]
+    fn test_corrupted_archive_fails_check() {
+        for account_id in ["alice.near", "a".repeat(64).as_str()] {
+            let account_id: AccountId = account_id.parse().unwrap();
+            let mut bytes = rkyv::to_bytes::<_, 64>(&account_id).unwrap();
+
+            let pos = bytes
+                .iter()
+                .position(|&b| b == b'a')
+                .expect("the Account ID bytes are stored in the archive");
+            bytes[pos] = b'A';
+
+            let err = rkyv::check_archived_root::<AccountId>(&bytes).unwrap_err();
+            assert!(err.to_string().contains("invalid Account ID"), "{}", err);
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use crate::AccountIdRef;
+//! Serde support for Account IDs, beyond the default string representation.
+
+use alloc::{boxed::Box, format};
+use core::fmt;
+
+use crate::{AccountIdRef, AccountType};
 
 use super::AccountId;
 
@@ -45,10 +50,199 @@ impl<'de> de::Deserialize<'de> for &'de AccountIdRef {
     }
 }
 
+impl ser::Serialize for AccountType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> de::Deserialize<'de> for AccountType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        const ACCOUNT_TYPES: &[&str] = &[
+            AccountType::NamedAccount.as_str(),
+            AccountType::NearImplicitAccount.as_str(),
+            AccountType::EthImplicitAccount.as_str(),
+            AccountType::NearDeterministicAccount.as_str(),
+        ];
+
+        struct AccountTypeVisitor;
+
+        impl<'de> de::Visitor<'de> for AccountTypeVisitor {
+            type Value = AccountType;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an account type")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| de::Error::unknown_variant(v, ACCOUNT_TYPES))
+            }
+        }
+
+        deserializer.deserialize_str(AccountTypeVisitor)
+    }
+}
+
+/// Serializes an Account ID as bytes for non-human-readable formats.
+///
+/// For use with `#[serde(with = "near_account_id::serde::as_bytes")]`.
+///
+/// Binary formats like bincode or MessagePack get the raw ASCII bytes of the Account ID,
+/// while human-readable formats like JSON still get a string. Deserialization accepts
+/// both forms, and validates the Account ID either way.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::AccountId;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Transfer {
+///     #[serde(with = "near_account_id::serde::as_bytes")]
+///     receiver_id: AccountId,
+/// }
+///
+/// let transfer: Transfer = serde_json::from_str(r#"{"receiver_id":"alice.near"}"#).unwrap();
+/// assert_eq!(transfer.receiver_id, "alice.near");
+/// ```
+pub mod as_bytes {
+    use alloc::{borrow::ToOwned, format, string::String};
+    use core::fmt;
+
+    use serde::{de, ser};
+
+    use crate::{AccountId, AccountIdRef};
+
+    pub fn serialize<S>(account_id: &AccountIdRef, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(account_id.as_str())
+        } else {
+            serializer.serialize_bytes(account_id.as_bytes())
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<AccountId, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            de::Deserialize::deserialize(deserializer)
+        } else {
+            deserializer.deserialize_bytes(BytesVisitor)
+        }
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> de::Visitor<'de> for BytesVisitor {
+        type Value = AccountId;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an Account ID as bytes")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            AccountIdRef::from_bytes(v)
+                .map(ToOwned::to_owned)
+                .map_err(|err| {
+                    de::Error::custom(format!(
+                        "invalid value: \"{}\", {}",
+                        String::from_utf8_lossy(v),
+                        err
+                    ))
+                })
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            self.visit_bytes(v.as_bytes())
+        }
+    }
+}
+
+/// Deserializes an empty string as `None`, for optional Account IDs in config files.
+///
+/// For use with `#[serde(with = "near_account_id::serde::empty_string_as_none")]` on an
+/// `Option<AccountId>` field. In human-readable formats like JSON, `null` is also deserialized
+/// as `None`. Any other string has to be a valid Account ID. `None` is serialized as an empty
+/// string, in every format. Combine it with
+/// `#[serde(default)]` to also allow the field to be missing.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::AccountId;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(default, with = "near_account_id::serde::empty_string_as_none")]
+///     owner_id: Option<AccountId>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"owner_id":""}"#).unwrap();
+/// assert_eq!(config.owner_id, None);
+///
+/// let config: Config = serde_json::from_str(r#"{"owner_id":"alice.near"}"#).unwrap();
+/// assert_eq!(config.owner_id.unwrap(), "alice.near");
+///
+/// assert!(serde_json::from_str::<Config>(r#"{"owner_id":"Alice.near"}"#).is_err());
+/// ```
+pub mod empty_string_as_none {
+    use alloc::{boxed::Box, format};
+
+    use serde::{de, ser};
+
+    use crate::AccountId;
+
+    pub fn serialize<S>(account_id: &Option<AccountId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match account_id {
+            Some(account_id) => serializer.serialize_str(account_id.as_str()),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<AccountId>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // `serialize` always writes a string, which self-describing formats can still read as
+        // an `Option`, to also accept `null`. Other formats need to read back exactly a string.
+        let account_id = if deserializer.is_human_readable() {
+            <Option<Box<str>> as de::Deserialize>::deserialize(deserializer)?
+        } else {
+            Some(<Box<str> as de::Deserialize>::deserialize(deserializer)?)
+        };
+        match account_id {
+            Some(account_id) if !account_id.is_empty() => {
+                crate::validation::validate(&account_id).map_err(|err| {
+                    de::Error::custom(format!("invalid value: \"{}\", {}", account_id, err))
+                })?;
+                Ok(Some(AccountId(account_id)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::count_alloc::count_allocs;
     use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
-    use crate::AccountId;
+    use crate::{AccountId, AccountIdRef, AccountType};
 
     use serde_json::json;
 
@@ -81,6 +275,157 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_borrowed_ref() {
+        let input = r#""alice.near""#;
+        let (account_id, allocs) =
+            count_allocs(|| serde_json::from_str::<&AccountIdRef>(input).unwrap());
+        assert_eq!(account_id, "alice.near");
+        assert_eq!(allocs, 0);
+        // The account ID borrows straight from the input.
+        assert!(input
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&account_id.as_bytes().as_ptr()));
+
+        // Escaped strings can't be borrowed, so this fails rather than allocating.
+        assert!(serde_json::from_str::<&AccountIdRef>(r#""alice\u002enear""#).is_err());
+
+        assert!(serde_json::from_str::<&AccountIdRef>(r#""Alice.near""#).is_err());
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Transfer {
+            #[serde(with = "crate::serde::as_bytes")]
+            receiver_id: AccountId,
+        }
+        // bincode's encoding of a `Transfer`: a `u64` length prefix followed by the bytes.
+        let encode = |bytes: &[u8]| [&(bytes.len() as u64).to_le_bytes()[..], bytes].concat();
+
+        for account_id in OK_ACCOUNT_IDS.iter() {
+            let transfer = Transfer {
+                receiver_id: account_id.parse().unwrap(),
+            };
+
+            let json = serde_json::to_value(&transfer).unwrap();
+            assert_eq!(json, json!({ "receiver_id": account_id }));
+            assert_eq!(serde_json::from_value::<Transfer>(json).unwrap(), transfer);
+
+            let bytes = bincode::serialize(&transfer).unwrap();
+            assert_eq!(&bytes[8..], account_id.as_bytes());
+            assert_eq!(bincode::deserialize::<Transfer>(&bytes).unwrap(), transfer);
+        }
+
+        for account_id in BAD_ACCOUNT_IDS.iter() {
+            assert!(
+                serde_json::from_value::<Transfer>(json!({ "receiver_id": account_id })).is_err(),
+                "successfully deserialized invalid account ID {:?}",
+                account_id
+            );
+            assert!(
+                bincode::deserialize::<Transfer>(&encode(account_id.as_bytes())).is_err(),
+                "successfully deserialized invalid account ID {:?}",
+                account_id
+            );
+        }
+
+        assert!(bincode::deserialize::<Transfer>(&encode(b"alice\xff.near")).is_err());
+    }
+
+    #[test]
+    fn test_empty_string_as_none_bincode() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::serde::empty_string_as_none")]
+            owner_id: Option<AccountId>,
+        }
+
+        for account_id in OK_ACCOUNT_IDS.iter() {
+            let config = Config {
+                owner_id: Some(account_id.parse().unwrap()),
+            };
+            let bytes = bincode::serialize(&config).unwrap();
+            assert_eq!(&bytes[8..], account_id.as_bytes());
+            assert_eq!(bincode::deserialize::<Config>(&bytes).unwrap(), config);
+        }
+
+        let config = Config { owner_id: None };
+        let bytes = bincode::serialize(&config).unwrap();
+        assert_eq!(bytes, 0u64.to_le_bytes());
+        assert_eq!(bincode::deserialize::<Config>(&bytes).unwrap(), config);
+
+        let bytes = bincode::serialize(&"Alice.near").unwrap();
+        assert!(bincode::deserialize::<Config>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_empty_string_as_none() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(default, with = "crate::serde::empty_string_as_none")]
+            owner_id: Option<AccountId>,
+        }
+
+        let config: Config = serde_json::from_value(json!({ "owner_id": "" })).unwrap();
+        assert_eq!(config.owner_id, None);
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            json!({ "owner_id": "" })
+        );
+
+        let config: Config = serde_json::from_value(json!({ "owner_id": null })).unwrap();
+        assert_eq!(config.owner_id, None);
+        let config: Config = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(config.owner_id, None);
+
+        for account_id in OK_ACCOUNT_IDS.iter() {
+            let json = json!({ "owner_id": account_id });
+            let config: Config = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(config.owner_id.as_deref().unwrap(), *account_id);
+            assert_eq!(serde_json::to_value(&config).unwrap(), json);
+        }
+
+        for account_id in BAD_ACCOUNT_IDS
+            .iter()
+            .filter(|account_id| !account_id.is_empty())
+        {
+            let err =
+                serde_json::from_value::<Config>(json!({ "owner_id": account_id })).unwrap_err();
+            assert!(
+                err.to_string().starts_with("invalid value"),
+                "{:?}: {}",
+                account_id,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_type() {
+        let account_types = [
+            (AccountType::NamedAccount, "named"),
+            (AccountType::NearImplicitAccount, "near-implicit"),
+            (AccountType::EthImplicitAccount, "eth-implicit"),
+            (AccountType::NearDeterministicAccount, "near-deterministic"),
+        ];
+        for (account_type, name) in account_types {
+            let value = serde_json::to_value(account_type).unwrap();
+            assert_eq!(value, json!(name));
+            assert_eq!(
+                serde_json::from_value::<AccountType>(value).unwrap(),
+                account_type
+            );
+        }
+
+        let err = serde_json::from_value::<AccountType>(json!("NamedAccount")).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown variant `NamedAccount`"));
+        assert!(serde_json::from_value::<AccountType>(json!(0)).is_err());
+    }
+
     #[test]
     fn fuzz() {
         bolero::check!().for_each(|input: &[u8]| {
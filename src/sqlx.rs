@@ -0,0 +1,76 @@
+//! [sqlx](https://docs.rs/sqlx) support for Account IDs.
+//!
+//! [`AccountId`] is stored as the database's text type, so it works with any backend that can
+//! store a [`String`]. Decoding validates the Account ID, and an invalid stored value is
+//! reported as an error.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use near_account_id::AccountId;
+//! use sqlx::{Connection, SqliteConnection};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), sqlx::Error> {
+//! let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+//!
+//! let alice: AccountId = "alice.near".parse().unwrap();
+//! let (account_id,): (AccountId,) = sqlx::query_as("SELECT ?")
+//!     .bind(&alice)
+//!     .fetch_one(&mut conn)
+//!     .await?;
+//! assert_eq!(account_id, alice);
+//! # Ok(())
+//! # }
+//! ```
+
+use sqlx::database::Database;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Decode, Encode, Type};
+
+use crate::AccountId;
+
+impl<DB: Database> Type<DB> for AccountId
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for AccountId
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        // Backends may hold on to the argument, so it has to be owned.
+        <String as Encode<'q, DB>>::encode(self.as_str().into(), buf)
+    }
+
+    fn encode(self, buf: &mut <DB as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        <String as Encode<'q, DB>>::encode(self.0.into(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for AccountId
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let account_id = <String as Decode<'r, DB>>::decode(value)?;
+        Ok(AccountId::try_from(account_id)?)
+    }
+}
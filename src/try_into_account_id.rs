@@ -0,0 +1,204 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+};
+
+use crate::{AccountId, AccountIdRef, ParseAccountError};
+
+/// Conversion into an [`AccountId`], validating the input unless it's already an Account ID.
+///
+/// This is implemented for the string types Account IDs usually come from, so that functions
+/// can accept any of them. Owned strings are validated in place and their allocation is reused.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{AccountId, TryIntoAccountId};
+///
+/// fn receiver(account_id: impl TryIntoAccountId) -> AccountId {
+///     account_id.try_into_account_id().unwrap()
+/// }
+///
+/// assert_eq!(receiver("alice.near"), "alice.near");
+/// assert_eq!(receiver(String::from("alice.near")), "alice.near");
+/// assert_eq!(receiver(Box::<str>::from("alice.near")), "alice.near");
+/// ```
+pub trait TryIntoAccountId {
+    /// Converts `self` into an [`AccountId`].
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError>;
+
+    /// Validates `self` and borrows it as an [`AccountIdRef`], without allocating.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use near_account_id::TryIntoAccountId;
+    ///
+    /// let input = String::from("alice.near");
+    /// let alice = input.try_into_account_id_ref().unwrap();
+    /// assert_eq!(alice.get_parent_account_id().unwrap(), "near");
+    /// ```
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError>;
+}
+
+impl TryIntoAccountId for AccountId {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        Ok(self)
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        Ok(self)
+    }
+}
+
+impl TryIntoAccountId for &AccountIdRef {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        Ok(self.to_owned())
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        Ok(self)
+    }
+}
+
+impl TryIntoAccountId for Cow<'_, AccountIdRef> {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        Ok(self.into_owned())
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        Ok(self)
+    }
+}
+
+impl TryIntoAccountId for &str {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        self.parse()
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        AccountIdRef::new(self)
+    }
+}
+
+impl TryIntoAccountId for String {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        self.try_into()
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        AccountIdRef::new(self)
+    }
+}
+
+impl TryIntoAccountId for Box<str> {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        self.try_into()
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        AccountIdRef::new(self)
+    }
+}
+
+impl TryIntoAccountId for Cow<'_, str> {
+    fn try_into_account_id(self) -> Result<AccountId, ParseAccountError> {
+        match self {
+            Cow::Borrowed(account_id) => account_id.try_into_account_id(),
+            Cow::Owned(account_id) => account_id.try_into_account_id(),
+        }
+    }
+
+    fn try_into_account_id_ref(&self) -> Result<&AccountIdRef, ParseAccountError> {
+        AccountIdRef::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::count_alloc::count_allocs;
+    use crate::ParseErrorKind;
+
+    #[test]
+    fn test_try_into_account_id() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+
+        assert_eq!(alice.to_owned().try_into_account_id().unwrap(), alice);
+        assert_eq!(alice.try_into_account_id().unwrap(), alice);
+        assert_eq!(Cow::Borrowed(alice).try_into_account_id().unwrap(), alice);
+        assert_eq!("alice.near".try_into_account_id().unwrap(), alice);
+        assert_eq!(
+            String::from("alice.near").try_into_account_id().unwrap(),
+            alice
+        );
+        assert_eq!(
+            Box::<str>::from("alice.near")
+                .try_into_account_id()
+                .unwrap(),
+            alice
+        );
+        assert_eq!(
+            Cow::Borrowed("alice.near").try_into_account_id().unwrap(),
+            alice
+        );
+        assert_eq!(
+            Cow::<str>::Owned(String::from("alice.near"))
+                .try_into_account_id()
+                .unwrap(),
+            alice
+        );
+
+        let err = Cow::Borrowed("alice..near")
+            .try_into_account_id()
+            .unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+        let err = Box::<str>::from("Alice.near")
+            .try_into_account_id()
+            .unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    }
+
+    #[test]
+    fn test_try_into_account_id_ref() {
+        fn assert_borrowed(input: &impl TryIntoAccountId, expected: &str) {
+            let (account_id, allocs) = count_allocs(|| input.try_into_account_id_ref().unwrap());
+            assert_eq!(allocs, 0);
+            assert_eq!(account_id, expected);
+            assert_eq!(account_id.as_bytes().as_ptr(), expected.as_ptr());
+        }
+
+        let input = String::from("alice.near");
+        assert_borrowed(&input.as_str(), &input);
+        assert_borrowed(&input, &input);
+        assert_borrowed(&Cow::Borrowed(input.as_str()), &input);
+
+        let input = Box::<str>::from("alice.near");
+        assert_borrowed(&input, &input);
+
+        let input = AccountId::try_from(String::from("alice.near")).unwrap();
+        assert_borrowed(&input, input.as_str());
+        assert_borrowed(&&*input, input.as_str());
+        assert_borrowed(&Cow::Borrowed(&*input), input.as_str());
+
+        let err = "alice..near".try_into_account_id_ref().unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+    }
+
+    #[test]
+    fn test_no_unnecessary_clone() {
+        let boxed = Box::<str>::from("alice.near");
+        let ptr = boxed.as_ptr();
+        let (account_id, allocs) = count_allocs(|| boxed.try_into_account_id().unwrap());
+        assert_eq!(allocs, 0);
+        assert_eq!(account_id.as_bytes().as_ptr(), ptr);
+
+        let owned = Cow::<str>::Owned(String::from("alice.near"));
+        let ptr = owned.as_ptr();
+        let (account_id, allocs) = count_allocs(|| owned.try_into_account_id().unwrap());
+        assert_eq!(allocs, 0);
+        assert_eq!(account_id.as_bytes().as_ptr(), ptr);
+    }
+}
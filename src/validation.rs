@@ -1,9 +1,99 @@
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use alloc::{string::String, vec::Vec};
+
 use crate::{ParseAccountError, ParseErrorKind};
 
 /// Shortest valid length for a NEAR Account ID.
 pub const MIN_LEN: usize = 2;
 /// Longest valid length for a NEAR Account ID.
 pub const MAX_LEN: usize = 64;
+/// Most labels a valid NEAR Account ID can have.
+///
+/// Every label is at least one character, and all but the first are preceded by a `.`.
+pub const MAX_LABELS: usize = (MAX_LEN - 1) / 2 + 1;
+/// Shortest top-level Account ID that can be created by anyone, instead of only the registrar.
+///
+/// This is the `min_allowed_top_level_account_length` of the protocol's runtime config.
+pub const MIN_ALLOWED_TOP_LEVEL_ACCOUNT_LEN: usize = 32;
+
+/// A regular expression matching the grammar of NEAR Account IDs.
+///
+/// This doesn't enforce the length limits, so the length of an Account ID has to be checked
+/// against [`AccountId::MIN_LEN`](crate::AccountId::MIN_LEN) and
+/// [`AccountId::MAX_LEN`](crate::AccountId::MAX_LEN) separately.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::ACCOUNT_ID_PATTERN;
+///
+/// assert!(ACCOUNT_ID_PATTERN.starts_with('^') && ACCOUNT_ID_PATTERN.ends_with('$'));
+/// ```
+pub const ACCOUNT_ID_PATTERN: &str = r"^(([a-z\d]+[-_])*[a-z\d]+\.)*([a-z\d]+[-_])*[a-z\d]+$";
+
+/// Every character allowed in a NEAR Account ID.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{AccountId, ACCOUNT_ID_CHARSET};
+///
+/// assert!("alice.near".bytes().all(|b| ACCOUNT_ID_CHARSET.contains(&b)));
+/// assert!(!ACCOUNT_ID_CHARSET.contains(&b'A'));
+/// ```
+pub const ACCOUNT_ID_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-_.";
+
+/// The characters of [`ACCOUNT_ID_CHARSET`] that separate the parts of a NEAR Account ID.
+///
+/// A separator can't start or end an Account ID, nor immediately follow another separator.
+pub const SEPARATORS: &[u8] = b"-_.";
+
+#[derive(Eq, Clone, Copy, PartialEq)]
+enum CharClass {
+    Invalid,
+    Alphanumeric,
+    Separator,
+}
+
+/// The [`CharClass`] of every byte, derived from [`ACCOUNT_ID_CHARSET`] and [`SEPARATORS`].
+const CHAR_CLASSES: [CharClass; 256] = {
+    let mut classes = [CharClass::Invalid; 256];
+    let mut i = 0;
+    while i < ACCOUNT_ID_CHARSET.len() {
+        classes[ACCOUNT_ID_CHARSET[i] as usize] = CharClass::Alphanumeric;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < SEPARATORS.len() {
+        classes[SEPARATORS[i] as usize] = CharClass::Separator;
+        i += 1;
+    }
+    classes
+};
+
+#[inline]
+const fn char_class(b: u8) -> CharClass {
+    CHAR_CLASSES[b as usize]
+}
+
+#[inline]
+fn char_class_of(c: char) -> CharClass {
+    u8::try_from(c).map_or(CharClass::Invalid, char_class)
+}
+
+#[inline]
+pub(crate) const fn is_separator(b: u8) -> bool {
+    matches!(char_class(b), CharClass::Separator)
+}
+
+/// Account IDs that are syntactically valid, but reserved by the protocol.
+const RESERVED_ACCOUNT_IDS: &[&str] = &["system"];
+
+pub fn is_reserved(account_id: &str) -> bool {
+    RESERVED_ACCOUNT_IDS.contains(&account_id)
+}
 
 pub const fn validate_const(account_id: &str) {
     const fn validate_format_const(id: &[u8], idx: usize, current_char_is_separator: bool) {
@@ -14,9 +104,9 @@ pub const fn validate_const(account_id: &str) {
             return;
         }
 
-        match id[idx] {
-            b'a'..=b'z' | b'0'..=b'9' => validate_format_const(id, idx + 1, false),
-            b'-' | b'_' | b'.' => {
+        match char_class(id[idx]) {
+            CharClass::Alphanumeric => validate_format_const(id, idx + 1, false),
+            CharClass::Separator => {
                 if current_char_is_separator {
                     panic!("NEAR Account ID cannot contain redundant separator (-, _, .)")
                 } else if idx == 0 {
@@ -25,7 +115,7 @@ pub const fn validate_const(account_id: &str) {
                     validate_format_const(id, idx + 1, true)
                 }
             }
-            _ => panic!(
+            CharClass::Invalid => panic!(
                 "NEAR Account ID cannot contain invalid chars (only a-z, 0-9, -, _, and . are allowed)"
             ),
         }
@@ -40,65 +130,487 @@ pub const fn validate_const(account_id: &str) {
     validate_format_const(account_id.as_bytes(), 0, false);
 }
 
+/// Same as [`validate`], but usable in const context, only reporting the kind of the violation.
+pub const fn validate_const_result(account_id: &str) -> Result<(), ParseErrorKind> {
+    let id = account_id.as_bytes();
+
+    if id.len() < MIN_LEN {
+        return Err(ParseErrorKind::TooShort);
+    } else if id.len() > MAX_LEN {
+        return Err(ParseErrorKind::TooLong);
+    }
+
+    let mut last_char_is_separator = true;
+    let mut idx = 0;
+    while idx < id.len() {
+        let current_char_is_separator = match char_class(id[idx]) {
+            CharClass::Alphanumeric => false,
+            CharClass::Separator => true,
+            CharClass::Invalid => return Err(ParseErrorKind::InvalidChar),
+        };
+        if current_char_is_separator && last_char_is_separator {
+            return Err(ParseErrorKind::RedundantSeparator);
+        }
+        last_char_is_separator = current_char_is_separator;
+        idx += 1;
+    }
+
+    if last_char_is_separator {
+        return Err(ParseErrorKind::RedundantSeparator);
+    }
+    Ok(())
+}
+
+#[inline]
 pub fn validate(account_id: &str) -> Result<(), ParseAccountError> {
+    validate_with_limits(account_id, MAX_LEN)
+}
+
+/// Same as [`validate`], but also rejects Account IDs reserved by the protocol.
+pub fn validate_strict(account_id: &str) -> Result<(), ParseAccountError> {
+    validate(account_id)?;
+    if is_reserved(account_id) {
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::Reserved,
+            char: None,
+        });
+    }
+    Ok(())
+}
+
+/// Same as [`validate`], but with a custom upper bound on the Account ID length.
+///
+/// Implicit accounts are unaffected by the limit: they are always exactly 64 (NEAR-implicit)
+/// or 42 (ETH-implicit) characters long, so longer hex strings are plain named accounts.
+#[inline]
+pub fn validate_with_limits(account_id: &str, max_len: usize) -> Result<(), ParseAccountError> {
+    validate_bytes_with_limits(account_id.as_bytes(), max_len)
+}
+
+/// Same as [`validate`], but operating directly on bytes.
+///
+/// Since the allowed alphabet is pure ASCII, any non-ASCII byte is reported as
+/// [`ParseErrorKind::InvalidChar`], and a successfully validated slice is always valid UTF-8.
+pub fn validate_bytes(account_id: &[u8]) -> Result<(), ParseAccountError> {
+    validate_bytes_with_limits(account_id, MAX_LEN)
+}
+
+#[inline]
+fn validate_bytes_with_limits(account_id: &[u8], max_len: usize) -> Result<(), ParseAccountError> {
     if account_id.len() < MIN_LEN {
         Err(ParseAccountError {
             kind: ParseErrorKind::TooShort,
             char: None,
         })
-    } else if account_id.len() > MAX_LEN {
+    } else if account_id.len() > max_len {
         Err(ParseAccountError {
             kind: ParseErrorKind::TooLong,
             char: None,
         })
+    } else if account_id.len() == 64 && is_lowercase_alphanumeric(account_id) {
+        // Fast path for NEAR-implicit sized account IDs: without separators, only the alphabet
+        // needs checking, which doesn't need the state machine below.
+        Ok(())
     } else {
-        // Adapted from https://github.com/near/near-sdk-rs/blob/fd7d4f82d0dfd15f824a1cf110e552e940ea9073/near-sdk/src/environment/env.rs#L819
-
-        // NOTE: We don't want to use Regex here, because it requires extra time to compile it.
-        // The valid account ID regex is /^(([a-z\d]+[-_])*[a-z\d]+\.)*([a-z\d]+[-_])*[a-z\d]+$/
-        // Instead the implementation is based on the previous character checks.
-
-        // We can safely assume that last char was a separator.
-        let mut last_char_is_separator = true;
-
-        let mut this = None;
-        for (i, c) in account_id.chars().enumerate() {
-            this.replace((i, c));
-            let current_char_is_separator = match c {
-                'a'..='z' | '0'..='9' => false,
-                '-' | '_' | '.' => true,
-                _ => {
-                    return Err(ParseAccountError {
-                        kind: ParseErrorKind::InvalidChar,
-                        char: this,
-                    });
-                }
-            };
-            if current_char_is_separator && last_char_is_separator {
+        validate_format(account_id)
+    }
+}
+
+/// Checks the characters and separators of an Account ID, but not its length.
+///
+/// Errors are reported at the first violation, which is always tied to a character. An empty
+/// input passes.
+#[inline]
+pub(crate) fn validate_format(account_id: &[u8]) -> Result<(), ParseAccountError> {
+    // Adapted from https://github.com/near/near-sdk-rs/blob/fd7d4f82d0dfd15f824a1cf110e552e940ea9073/near-sdk/src/environment/env.rs#L819
+
+    // NOTE: We don't want to use Regex here, because it requires extra time to compile it.
+    // The valid account ID regex is `ACCOUNT_ID_PATTERN`.
+    // Instead the implementation is based on the previous character checks.
+
+    // NOTE: Every byte preceding the first violation is valid ASCII, so byte offsets
+    // reported in errors are also char offsets.
+
+    // We can safely assume that last char was a separator.
+    let mut last_char_is_separator = true;
+
+    for (i, &b) in account_id.iter().enumerate() {
+        let current_char_is_separator = match char_class(b) {
+            CharClass::Alphanumeric => false,
+            CharClass::Separator => true,
+            CharClass::Invalid => {
                 return Err(ParseAccountError {
-                    kind: ParseErrorKind::RedundantSeparator,
-                    char: this,
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((i, char_at(account_id, i))),
                 });
             }
-            last_char_is_separator = current_char_is_separator;
+        };
+        if current_char_is_separator && last_char_is_separator {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: Some((i, b as char)),
+            });
         }
+        last_char_is_separator = current_char_is_separator;
+    }
+
+    if last_char_is_separator && !account_id.is_empty() {
+        let last = account_id.len() - 1;
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: Some((last, account_id[last] as char)),
+        });
+    }
+    Ok(())
+}
+
+/// Returns `true` if all bytes are lowercase ASCII letters or digits.
+///
+/// This deliberately doesn't short-circuit, so that the compiler can vectorize it.
+#[inline]
+fn is_lowercase_alphanumeric(bytes: &[u8]) -> bool {
+    bytes.iter().fold(true, |acc, &b| {
+        acc & (b.is_ascii_lowercase() | b.is_ascii_digit())
+    })
+}
 
-        if last_char_is_separator {
+/// Validates a single label of an Account ID, i.e. a part between `.` separators.
+///
+/// A label follows the same rules as an Account ID, except that it may be a single character
+/// long and must not contain a `.`, which is reported as [`ParseErrorKind::InvalidChar`].
+pub fn validate_label(label: &str) -> Result<(), ParseAccountError> {
+    let bytes = label.as_bytes();
+    if bytes.is_empty() {
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::TooShort,
+            char: None,
+        });
+    } else if bytes.len() > MAX_LEN {
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::TooLong,
+            char: None,
+        });
+    }
+
+    let mut last_char_is_separator = true;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        let current_char_is_separator = match char_class(b) {
+            CharClass::Alphanumeric => false,
+            // A `.` would separate two labels.
+            CharClass::Separator if b != b'.' => true,
+            _ => {
+                return Err(ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((i, char_at(bytes, i))),
+                });
+            }
+        };
+        if current_char_is_separator && last_char_is_separator {
             return Err(ParseAccountError {
                 kind: ParseErrorKind::RedundantSeparator,
-                char: this,
+                char: Some((i, b as char)),
             });
         }
+        last_char_is_separator = current_char_is_separator;
+    }
+
+    if last_char_is_separator {
+        let last = bytes.len() - 1;
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: Some((last, bytes[last] as char)),
+        });
+    }
+    Ok(())
+}
+
+/// Decodes the char starting at byte `idx`, falling back to [`char::REPLACEMENT_CHARACTER`]
+/// if the bytes are not valid UTF-8.
+fn char_at(bytes: &[u8], idx: usize) -> char {
+    let tail = &bytes[idx..bytes.len().min(idx + 4)];
+    let valid = match core::str::from_utf8(tail) {
+        Ok(valid) => valid,
+        Err(err) => core::str::from_utf8(&tail[..err.valid_up_to()]).unwrap_or_default(),
+    };
+    valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// An incremental Account ID validator, for input that arrives in chunks.
+///
+/// Feeding an Account ID to [`Validator::push`] in any number of chunks and then calling
+/// [`Validator::finish`] accepts exactly the Account IDs that
+/// [`AccountId::validate`](crate::AccountId::validate) accepts.
+///
+/// Since the total length isn't known up front, the first violation in the input is reported,
+/// even if the Account ID turns out to be too short or too long. Error positions are byte offsets into
+/// the whole input, but an invalid char split across chunks is reported as
+/// [`char::REPLACEMENT_CHARACTER`]. Once an error is reported, it's reported again by every
+/// subsequent call.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::{ParseErrorKind, Validator};
+///
+/// let mut validator = Validator::new();
+/// validator.push(b"alice").unwrap();
+/// validator.push(b".near").unwrap();
+/// assert!(validator.finish().is_ok());
+///
+/// let mut validator = Validator::new();
+/// validator.push(b"alice.").unwrap();
+/// let err = validator.push(b".near").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    len: usize,
+    last: Option<u8>,
+    error: Option<ParseAccountError>,
+}
+
+impl Validator {
+    /// Creates a validator for a new Account ID.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates the next chunk of the Account ID.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<(), ParseAccountError> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+
+        // Same state machine as in `validate`, carried over between chunks.
+        for (i, &b) in bytes.iter().enumerate() {
+            let idx = self.len + i;
+            let err = if idx >= MAX_LEN {
+                Some(ParseAccountError {
+                    kind: ParseErrorKind::TooLong,
+                    char: None,
+                })
+            } else {
+                let last_char_is_separator = match self.last {
+                    Some(last) => is_separator(last),
+                    None => true,
+                };
+                match char_class(b) {
+                    CharClass::Alphanumeric => None,
+                    CharClass::Separator if !last_char_is_separator => None,
+                    CharClass::Separator => Some(ParseAccountError {
+                        kind: ParseErrorKind::RedundantSeparator,
+                        char: Some((idx, b as char)),
+                    }),
+                    CharClass::Invalid => Some(ParseAccountError {
+                        kind: ParseErrorKind::InvalidChar,
+                        char: Some((idx, char_at(bytes, i))),
+                    }),
+                }
+            };
+            if let Some(err) = err {
+                self.error = Some(err.clone());
+                return Err(err);
+            }
+            self.last = Some(b);
+        }
+        self.len += bytes.len();
         Ok(())
     }
+
+    /// Finishes validating the Account ID, checking its length and its last character.
+    pub fn finish(self) -> Result<(), ParseAccountError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        match self.last {
+            _ if self.len < MIN_LEN => Err(ParseAccountError {
+                kind: ParseErrorKind::TooShort,
+                char: None,
+            }),
+            Some(last) if is_separator(last) => Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: Some((self.len - 1, last as char)),
+            }),
+            _ => Ok(()),
+        }
+    }
 }
 
+/// Lazily validates every line of `reader` as an Account ID, yielding each line along with
+/// its validation result.
+///
+/// Both `\n` and `\r\n` line endings are accepted. Lines that are not valid UTF-8 are
+/// reported as [`ParseErrorKind::InvalidChar`] and yielded lossily converted. Iteration stops
+/// at the first I/O error.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::validate_lines;
+///
+/// let dump = "alice.near\nAlice.near\nbob.near\n";
+///
+/// let invalid: Vec<String> = validate_lines(dump.as_bytes())
+///     .filter(|(_, result)| result.is_err())
+///     .map(|(line, _)| line)
+///     .collect();
+///
+/// assert_eq!(invalid, ["Alice.near"]);
+/// ```
+#[cfg(feature = "std")]
+pub fn validate_lines<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = (String, Result<(), ParseAccountError>)> {
+    reader.split(b'\n').map_while(Result::ok).map(|mut line| {
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        let result = validate_bytes(&line);
+        let line = match String::from_utf8(line) {
+            Ok(line) => line,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        };
+        (line, result)
+    })
+}
+
+pub fn validate_all(account_id: &str) -> Result<(), Vec<ParseAccountError>> {
+    let mut errors = Vec::new();
+
+    if account_id.len() < MIN_LEN {
+        errors.push(ParseAccountError {
+            kind: ParseErrorKind::TooShort,
+            char: None,
+        });
+    } else if account_id.len() > MAX_LEN {
+        errors.push(ParseAccountError {
+            kind: ParseErrorKind::TooLong,
+            char: None,
+        });
+    }
+
+    // Same state machine as in `validate`, except that invalid characters are treated as
+    // non-separators so that a single bad char doesn't cascade into separator errors.
+    let mut last_char_is_separator = true;
+
+    let mut this = None;
+    for (i, c) in account_id.chars().enumerate() {
+        this.replace((i, c));
+        match char_class_of(c) {
+            CharClass::Alphanumeric => last_char_is_separator = false,
+            CharClass::Separator => {
+                if last_char_is_separator {
+                    errors.push(ParseAccountError {
+                        kind: ParseErrorKind::RedundantSeparator,
+                        char: this,
+                    });
+                }
+                last_char_is_separator = true;
+            }
+            CharClass::Invalid => {
+                errors.push(ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: this,
+                });
+                last_char_is_separator = false;
+            }
+        }
+    }
+
+    // A trailing separator is only reported once, even if it was already redundant.
+    if last_char_is_separator && this.is_some() && errors.last().map(|err| err.char) != Some(this) {
+        errors.push(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: this,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Applies simple repairs to an invalid Account ID: lowercasing it, collapsing runs of
+/// separators into their first separator, and trimming whitespace and separators off the edges.
+pub fn repair(account_id: &str) -> String {
+    let mut repaired = String::with_capacity(account_id.len());
+    let mut last_char_is_separator = true;
+    for c in account_id.trim().chars().flat_map(char::to_lowercase) {
+        let current_char_is_separator = char_class_of(c) == CharClass::Separator;
+        if !(current_char_is_separator && last_char_is_separator) {
+            repaired.push(c);
+            last_char_is_separator = current_char_is_separator;
+        }
+    }
+    if last_char_is_separator {
+        repaired.pop();
+    }
+    repaired
+}
+
+/// Returns `true` if the string is an ETH-implicit account ID, i.e. a 40 characters long
+/// lowercase hexadecimal prefixed with `0x`.
+///
+/// This classifies raw strings without constructing an [`AccountId`](crate::AccountId).
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::is_eth_implicit;
+///
+/// assert!(is_eth_implicit("0xb794f5ea0ba39494ce839613fffba74279579268"));
+/// assert!(!is_eth_implicit("0xB794F5EA0BA39494CE839613FFFBA74279579268"));
+/// assert!(!is_eth_implicit("alice.near"));
+/// ```
 pub fn is_eth_implicit(account_id: &str) -> bool {
     account_id.len() == 42
         && account_id.starts_with("0x")
-        && account_id[2..].as_bytes().iter().all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
+        && account_id.as_bytes()[2..]
+            .iter()
+            .all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
+}
+
+/// Returns `true` if the string is a NEAR-deterministic account ID, i.e. a 40 characters long
+/// lowercase hexadecimal prefixed with `0s`.
+///
+/// NEAR-deterministic account IDs are valid Account IDs, but are classified as a distinct
+/// [`AccountType::NearDeterministicAccount`](crate::AccountType::NearDeterministicAccount)
+/// rather than as named accounts.
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::is_near_deterministic;
+///
+/// assert!(is_near_deterministic("0s7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b"));
+/// assert!(!is_near_deterministic("0x7d8a1e5b4c3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b"));
+/// assert!(!is_near_deterministic("alice.near"));
+/// ```
+pub fn is_near_deterministic(account_id: &str) -> bool {
+    account_id.len() == 42
+        && account_id.starts_with("0s")
+        && account_id.as_bytes()[2..]
+            .iter()
+            .all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
 }
 
+/// Returns `true` if the string is a NEAR-implicit account ID, i.e. a 64 characters long
+/// lowercase hexadecimal.
+///
+/// This classifies raw strings without constructing an [`AccountId`](crate::AccountId).
+///
+/// ## Examples
+///
+/// ```
+/// use near_account_id::is_near_implicit;
+///
+/// assert!(is_near_implicit("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"));
+/// assert!(!is_near_implicit("alice.near"));
+/// ```
 pub fn is_near_implicit(account_id: &str) -> bool {
     account_id.len() == 64
         && account_id
@@ -107,12 +619,99 @@ pub fn is_near_implicit(account_id: &str) -> bool {
             .all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
 }
 
+/// Same as [`is_near_implicit`], but usable in const contexts.
+pub const fn is_near_implicit_const(account_id: &str) -> bool {
+    let bytes = account_id.as_bytes();
+    if bytes.len() != 64 {
+        return false;
+    }
+
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !matches!(bytes[idx], b'a'..=b'f' | b'0'..=b'9') {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
 
+    #[test]
+    fn test_is_near_implicit_const() {
+        for account_id in OK_ACCOUNT_IDS.into_iter().chain(BAD_ACCOUNT_IDS) {
+            assert_eq!(
+                is_near_implicit_const(account_id),
+                is_near_implicit(account_id),
+                "{:?}",
+                account_id
+            );
+        }
+        assert!(is_near_implicit_const(&"f".repeat(64)));
+        assert!(!is_near_implicit_const(&"g".repeat(64)));
+        assert!(!is_near_implicit_const(&"f".repeat(63)));
+    }
+
+    #[test]
+    fn test_validator() {
+        for account_id in OK_ACCOUNT_IDS.into_iter().chain(BAD_ACCOUNT_IDS) {
+            let bytes = account_id.as_bytes();
+            let expected = validate(account_id);
+
+            for split in 0..=bytes.len() {
+                let mut validator = Validator::new();
+                let result = validator
+                    .push(&bytes[..split])
+                    .and_then(|()| validator.push(&bytes[split..]))
+                    .and_then(|()| validator.clone().finish());
+
+                assert_eq!(result.is_ok(), expected.is_ok(), "{:?}", account_id);
+                if let (Err(result), Err(expected), true) = (
+                    result,
+                    &expected,
+                    (MIN_LEN..=MAX_LEN).contains(&bytes.len()),
+                ) {
+                    assert_eq!(result.kind, expected.kind, "{:?}", account_id);
+                    assert_eq!(
+                        result.char.map(|(idx, _)| idx),
+                        expected.char.map(|(idx, _)| idx),
+                        "{:?}",
+                        account_id
+                    );
+                }
+            }
+        }
+
+        let mut validator = Validator::new();
+        for _ in 0..MAX_LEN {
+            validator.push(b"a").unwrap();
+        }
+        let err = validator.push(b"a").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::TooLong);
+        assert_eq!(validator.push(b"").unwrap_err(), err);
+        assert_eq!(validator.finish().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_account_id_pattern() {
+        let regex = regex::Regex::new(ACCOUNT_ID_PATTERN).unwrap();
+        for account_id in OK_ACCOUNT_IDS.into_iter().chain(BAD_ACCOUNT_IDS) {
+            let matches =
+                regex.is_match(account_id) && (MIN_LEN..=MAX_LEN).contains(&account_id.len());
+            assert_eq!(
+                matches,
+                validate(account_id).is_ok(),
+                "{:?} doesn't agree with the pattern",
+                account_id
+            );
+        }
+    }
+
     #[test]
     fn test_is_valid_account_id() {
         for account_id in OK_ACCOUNT_IDS {
@@ -133,6 +732,270 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_validate_bytes() {
+        for account_id in OK_ACCOUNT_IDS.iter().chain(BAD_ACCOUNT_IDS.iter()) {
+            assert_eq!(validate_bytes(account_id.as_bytes()), validate(account_id));
+        }
+
+        assert_eq!(
+            validate_bytes(b"alice\xff.near"),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::InvalidChar,
+                char: Some((5, char::REPLACEMENT_CHARACTER)),
+            })
+        );
+        assert_eq!(
+            validate_bytes("aƒ\u{1F600}".as_bytes()),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::InvalidChar,
+                char: Some((1, 'ƒ')),
+            })
+        );
+        assert_eq!(
+            validate_bytes(b"ab\xf0\x9f\x98"),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::InvalidChar,
+                char: Some((2, char::REPLACEMENT_CHARACTER)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_near_implicit_sized() {
+        let near_implicit = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+        assert_eq!(validate(near_implicit), Ok(()));
+        assert_eq!(validate(&"z".repeat(64)), Ok(()));
+
+        // Everything rejected by the fast path still goes through the full state machine.
+        assert_eq!(
+            validate(&format!(".{}", "a".repeat(63))).unwrap_err().char,
+            Some((0, '.'))
+        );
+        assert_eq!(
+            validate(&"a-".repeat(32)).unwrap_err().char,
+            Some((63, '-'))
+        );
+        assert_eq!(validate(&"ab".repeat(32)), Ok(()));
+        let mut upper = near_implicit.to_string();
+        upper.replace_range(10..11, "A");
+        assert_eq!(
+            validate(&upper).unwrap_err(),
+            ParseAccountError {
+                kind: ParseErrorKind::InvalidChar,
+                char: Some((10, 'A')),
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_label() {
+        let max = "a".repeat(MAX_LEN);
+        for label in ["a", "a-b", "alice", "app-1", "a_b-c", "0x1", &max] {
+            assert_eq!(validate_label(label), Ok(()), "{:?}", label);
+        }
+
+        let too_long = "a".repeat(MAX_LEN + 1);
+        let cases = [
+            ("", ParseErrorKind::TooShort, None),
+            (&too_long, ParseErrorKind::TooLong, None),
+            ("-", ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+            ("-a", ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+            ("_alice", ParseErrorKind::RedundantSeparator, Some((0, '_'))),
+            ("alice-", ParseErrorKind::RedundantSeparator, Some((5, '-'))),
+            ("a-_b", ParseErrorKind::RedundantSeparator, Some((2, '_'))),
+            ("a.b", ParseErrorKind::InvalidChar, Some((1, '.'))),
+            ("app.alice", ParseErrorKind::InvalidChar, Some((3, '.'))),
+            ("Alice", ParseErrorKind::InvalidChar, Some((0, 'A'))),
+            ("ƒelicia", ParseErrorKind::InvalidChar, Some((0, 'ƒ'))),
+        ];
+        for (label, kind, char) in cases {
+            assert_eq!(
+                validate_label(label),
+                Err(ParseAccountError { kind, char }),
+                "{:?}",
+                label
+            );
+        }
+    }
+
+    #[test]
+    fn test_charset() {
+        assert!(SEPARATORS.iter().all(|b| ACCOUNT_ID_CHARSET.contains(b)));
+
+        for b in 0..=u8::MAX {
+            let in_charset = ACCOUNT_ID_CHARSET.contains(&b);
+            let account_id = [b'a', b, b'a'];
+            assert_eq!(
+                validate_bytes(&account_id).is_ok(),
+                in_charset,
+                "{:?}",
+                b as char
+            );
+
+            let separated = [b'a', b, b, b'a'];
+            let is_separator = SEPARATORS.contains(&b);
+            assert_eq!(
+                validate_bytes(&separated).is_ok(),
+                in_charset && !is_separator,
+                "{:?}",
+                b as char
+            );
+        }
+
+        let pattern = regex::Regex::new(ACCOUNT_ID_PATTERN).unwrap();
+        for &b in ACCOUNT_ID_CHARSET {
+            let account_id = [b'a', b, b'a'];
+            assert!(pattern.is_match(core::str::from_utf8(&account_id).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_validate_strict() {
+        for account_id in OK_ACCOUNT_IDS {
+            assert_eq!(
+                validate_strict(account_id).is_ok(),
+                !is_reserved(account_id),
+                "{}",
+                account_id
+            );
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            assert_eq!(validate_strict(account_id), validate(account_id));
+        }
+
+        assert!(validate("system").is_ok());
+        let err = validate_strict("system").unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::Reserved);
+        assert_eq!(err.to_string(), "the Account ID is reserved");
+        assert!(validate_strict("system.near").is_ok());
+    }
+
+    #[test]
+    fn test_validate_with_limits() {
+        let long_account_id = format!("{}.near", "a".repeat(MAX_LEN));
+
+        assert!(matches!(
+            validate(&long_account_id),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::TooLong,
+                ..
+            })
+        ));
+        assert_eq!(validate_with_limits(&long_account_id, 128), Ok(()));
+        assert!(matches!(
+            validate_with_limits(&long_account_id, MAX_LEN + 1),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::TooLong,
+                ..
+            })
+        ));
+
+        // Other violations are still reported with a higher limit.
+        assert!(matches!(
+            validate_with_limits(&format!("{}.", long_account_id), 128),
+            Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                ..
+            })
+        ));
+
+        // Implicit accounts stay fixed at 64 characters.
+        let long_hex = "0123456789abcdef".repeat(5);
+        assert_eq!(validate_with_limits(&long_hex, 128), Ok(()));
+        assert!(!is_near_implicit(&long_hex));
+        assert!(is_near_implicit(&long_hex[..64]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_validate_lines() {
+        let input = b"alice.near\r\nAlice.near\n\nbob..near\nb\xffb\ncarol.near";
+        let results: Vec<_> = validate_lines(std::io::Cursor::new(input)).collect();
+
+        let err = |kind, char| Err(ParseAccountError { kind, char });
+        assert_eq!(
+            results,
+            [
+                ("alice.near".to_string(), Ok(())),
+                (
+                    "Alice.near".to_string(),
+                    err(ParseErrorKind::InvalidChar, Some((0, 'A')))
+                ),
+                ("".to_string(), err(ParseErrorKind::TooShort, None)),
+                (
+                    "bob..near".to_string(),
+                    err(ParseErrorKind::RedundantSeparator, Some((4, '.')))
+                ),
+                (
+                    "b\u{FFFD}b".to_string(),
+                    err(
+                        ParseErrorKind::InvalidChar,
+                        Some((1, char::REPLACEMENT_CHARACTER))
+                    )
+                ),
+                ("carol.near".to_string(), Ok(())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_all() {
+        for account_id in OK_ACCOUNT_IDS {
+            assert_eq!(validate_all(account_id), Ok(()));
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let errors = validate_all(account_id).unwrap_err();
+            assert_eq!(
+                errors[0],
+                validate(account_id).unwrap_err(),
+                "first reported error of {:?} should match `validate`",
+                account_id
+            );
+        }
+
+        let err = |kind, char| ParseAccountError { kind, char };
+        assert_eq!(
+            validate_all("A__ƒƒluent."),
+            Err(vec![
+                err(ParseErrorKind::InvalidChar, Some((0, 'A'))),
+                err(ParseErrorKind::RedundantSeparator, Some((2, '_'))),
+                err(ParseErrorKind::InvalidChar, Some((3, 'ƒ'))),
+                err(ParseErrorKind::InvalidChar, Some((4, 'ƒ'))),
+                err(ParseErrorKind::RedundantSeparator, Some((10, '.'))),
+            ])
+        );
+        assert_eq!(
+            validate_all("a.."),
+            Err(vec![err(
+                ParseErrorKind::RedundantSeparator,
+                Some((2, '.'))
+            )])
+        );
+        assert_eq!(
+            validate_all("-"),
+            Err(vec![
+                err(ParseErrorKind::TooShort, None),
+                err(ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+            ])
+        );
+        assert_eq!(
+            validate_all(""),
+            Err(vec![err(ParseErrorKind::TooShort, None)])
+        );
+
+        let long_invalid = format!("{}@", "a".repeat(64));
+        assert_eq!(
+            validate_all(&long_invalid),
+            Err(vec![
+                err(ParseErrorKind::TooLong, None),
+                err(ParseErrorKind::InvalidChar, Some((64, '@'))),
+            ])
+        );
+    }
+
     #[test]
     fn test_is_valid_account_id_const() {
         for account_id in OK_ACCOUNT_IDS {
@@ -140,6 +1003,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_const_result() {
+        for account_id in OK_ACCOUNT_IDS.iter().chain(BAD_ACCOUNT_IDS.iter()) {
+            assert_eq!(
+                validate_const_result(account_id),
+                validate(account_id).map_err(|err| err.kind),
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
     #[test]
     fn test_is_invalid_account_id_const() {
         for account_id in BAD_ACCOUNT_IDS {
@@ -0,0 +1,95 @@
+//! Round-trips Account IDs through an in-memory SQLite database.
+//!
+//! Run with `cargo test --features sqlx,_sqlx-sqlite-tests --test sqlx`.
+
+use near_account_id::{AccountId, ParseAccountError};
+use sqlx::{Connection, SqliteConnection};
+
+const OK_ACCOUNT_IDS: &[&str] = &[
+    "aa",
+    "a-a",
+    "alice.near",
+    "sub.alice.near",
+    "0o0ooo00oo00o",
+    "b-o_b",
+    "10-4.8-2",
+    "fb9243ce.a-b-c.near",
+    "0xb794f5ea0ba39494ce839613fffba74279579268",
+    "0123456789012345678901234567890123456789012345678901234567890123",
+];
+
+const BAD_ACCOUNT_IDS: &[&str] = &[
+    "a",
+    "A",
+    "Abc",
+    "-near",
+    "near-",
+    "a..near",
+    "alice_.near",
+    "alice.near.",
+    "01234567890123456789012345678901234567890123456789012345678901234",
+];
+
+async fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE accounts (account_id TEXT NOT NULL)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn test_round_trip() {
+    let mut conn = connect().await;
+    for account_id in OK_ACCOUNT_IDS {
+        let account_id: AccountId = account_id.parse().unwrap();
+        sqlx::query("INSERT INTO accounts VALUES (?)")
+            .bind(&account_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO accounts VALUES (?)")
+            .bind(account_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+
+    let rows: Vec<(AccountId,)> = sqlx::query_as("SELECT account_id FROM accounts")
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+    let expected = OK_ACCOUNT_IDS.iter().flat_map(|account_id| [account_id; 2]);
+    assert!(rows.iter().map(|(account_id,)| account_id).eq(expected));
+}
+
+#[tokio::test]
+async fn test_invalid_stored_value() {
+    let mut conn = connect().await;
+    for account_id in BAD_ACCOUNT_IDS {
+        sqlx::query("DELETE FROM accounts")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO accounts VALUES (?)")
+            .bind(account_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let err = sqlx::query_as::<_, (AccountId,)>("SELECT account_id FROM accounts")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        let sqlx::Error::ColumnDecode { source, .. } = err else {
+            panic!("{:?}: unexpected error {}", account_id, err);
+        };
+        assert!(
+            source.is::<ParseAccountError>(),
+            "{:?}: {}",
+            account_id,
+            source
+        );
+    }
+}